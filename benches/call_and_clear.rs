@@ -0,0 +1,30 @@
+//! Compares a plain `CallStaticVoidMethodA` against `CallStaticVoidMethodA_and_clear` for a static method that does nothing.
+//!
+//! The JVM is created without `-Xcheck:jni`, because the plain call deliberately skips the exception check.
+//!
+//! Run with `cargo bench --features loadjvm --bench call_and_clear`, `JAVA_HOME` must be set.
+
+mod common;
+
+use common::measure;
+use jni_simple::*;
+use std::ptr::{null, null_mut};
+
+const ITERATIONS: u32 = 10_000_000;
+
+fn main() {
+    unsafe {
+        load_jvm_from_java_home().expect("failed to load jvm");
+        let (_, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &Vec::<String>::new()).expect("failed to create jvm");
+        let class = env.DefineClass_from_slice("CallAndClearBench", null_mut(), include_bytes!("../java_testcode/CallAndClearBench.class"));
+        assert!(!class.is_null());
+        let noop = env.GetStaticMethodID(class, "noop", "()V");
+        assert!(!noop.is_null());
+
+        measure("CallStaticVoidMethodA", ITERATIONS, "call", |_| env.CallStaticVoidMethodA(class, noop, null()));
+
+        measure("CallStaticVoidMethodA_and_clear", ITERATIONS, "call", |_| {
+            assert!(!env.CallStaticVoidMethodA_and_clear(class, noop, null()));
+        });
+    }
+}
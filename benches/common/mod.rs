@@ -0,0 +1,18 @@
+//! Helpers shared by the benchmarks.
+
+use std::time::{Duration, Instant};
+
+///
+/// Calls `f` with the index of each of the `iterations` and prints the average duration of one call per `unit`.
+///
+/// Returns the total duration.
+///
+pub fn measure(name: &str, iterations: u32, unit: &str, mut f: impl FnMut(u32)) -> Duration {
+    let start = Instant::now();
+    for index in 0..iterations {
+        f(index);
+    }
+    let elapsed = start.elapsed();
+    println!("{name}: {:?} per {unit}", elapsed / iterations);
+    elapsed
+}
@@ -0,0 +1,38 @@
+//! Compares passing a payload to java through a `byte[]` filled with `SetByteArrayRegion`
+//! against writing it into a direct `ByteBuffer` from a `DirectBufferPool`.
+//!
+//! Run with `cargo bench --features test-util --bench direct_buffer_pool`, `JAVA_HOME` must be set.
+
+mod common;
+
+use common::measure;
+use jni_simple::*;
+use std::hint::black_box;
+
+const ITERATIONS: u32 = 200;
+
+const PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
+
+fn main() {
+    unsafe {
+        let (_, env) = testing::test_vm();
+        let payload = vec![0x55u8; PAYLOAD_SIZE];
+        let pool = DirectBufferPool::new();
+
+        measure("byte[] + SetByteArrayRegion", ITERATIONS, &format!("{PAYLOAD_SIZE} byte payload"), |_| {
+            let array = env.NewByteArray(PAYLOAD_SIZE as jsize);
+            assert!(!array.is_null());
+            env.SetByteArrayRegion(array, 0, PAYLOAD_SIZE as jsize, payload.as_ptr().cast());
+            black_box(array);
+            env.DeleteLocalRef(array);
+        });
+
+        measure("DirectBufferPool", ITERATIONS, &format!("{PAYLOAD_SIZE} byte payload"), |_| {
+            let mut buffer = pool.acquire(&env, PAYLOAD_SIZE).expect("acquire failed");
+            buffer.as_mut_slice()[..PAYLOAD_SIZE].copy_from_slice(&payload);
+            black_box(buffer.java_buffer());
+        });
+
+        pool.destroy(&env);
+    }
+}
@@ -0,0 +1,34 @@
+//! Compares creating a jstring per call with `NewStringUTF` against looking it up in `InternedStrings`.
+//!
+//! Run with `cargo bench --features test-util --bench interned_strings`, `JAVA_HOME` must be set.
+
+mod common;
+
+use common::measure;
+use jni_simple::*;
+use std::hint::black_box;
+
+const ITERATIONS: u32 = 1_000_000;
+
+const STRINGS: [&str; 4] = ["GET", "Content-Type", "application/json", "Hello World"];
+
+fn main() {
+    unsafe {
+        let (_, env) = testing::test_vm();
+        let interned = InternedStrings::new();
+
+        measure("NewStringUTF", ITERATIONS, "call", |index| {
+            let s = STRINGS[index as usize % STRINGS.len()];
+            let string = env.NewStringUTF(s);
+            black_box(string);
+            env.DeleteLocalRef(string);
+        });
+
+        measure("InternedStrings::get", ITERATIONS, "call", |index| {
+            let s = STRINGS[index as usize % STRINGS.len()];
+            black_box(interned.get(&env, s));
+        });
+
+        interned.clear(&env);
+    }
+}
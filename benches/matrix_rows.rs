@@ -0,0 +1,49 @@
+//! Compares copying the rows of a `double[][]` with `GetDoubleArrayRegion` against `GetPrimitiveArrayCritical`.
+//!
+//! `get_double_matrix` uses the region copy, this benchmark shows that the critical access is not meaningfully faster.
+//!
+//! Run with `cargo bench --features test-util --bench matrix_rows`, `JAVA_HOME` must be set.
+
+mod common;
+
+use common::measure;
+use jni_simple::*;
+use std::hint::black_box;
+use std::ptr::null_mut;
+
+const ITERATIONS: u32 = 200;
+
+const ROWS: usize = 512;
+
+const COLUMNS: usize = 512;
+
+fn main() {
+    unsafe {
+        let (_, env) = testing::test_vm();
+        let row = vec![1.5f64; COLUMNS];
+        let rows = vec![row.as_slice(); ROWS];
+        let matrix = env.new_double_matrix(&rows);
+        assert!(!matrix.is_null());
+
+        measure("get_double_matrix (GetDoubleArrayRegion)", ITERATIONS, &format!("{ROWS}x{COLUMNS} matrix"), |_| {
+            black_box(env.get_double_matrix(matrix).expect("exception thrown"));
+        });
+
+        measure("GetPrimitiveArrayCritical", ITERATIONS, &format!("{ROWS}x{COLUMNS} matrix"), |_| {
+            let mut result = Vec::with_capacity(ROWS);
+            for index in 0..ROWS as jsize {
+                let row = env.GetObjectArrayElement(matrix, index);
+                let len = env.GetArrayLength(row) as usize;
+                let mut values = vec![0f64; len];
+                let critical = env.GetPrimitiveArrayCritical(row, null_mut());
+                values.copy_from_slice(std::slice::from_raw_parts(critical.cast::<f64>(), len));
+                env.ReleasePrimitiveArrayCritical(row, critical, JNI_ABORT);
+                env.DeleteLocalRef(row);
+                result.push(values);
+            }
+            black_box(result);
+        });
+
+        env.DeleteLocalRef(matrix);
+    }
+}
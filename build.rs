@@ -0,0 +1,27 @@
+//! Compiles the C shim of the `va_list` integration test into a shared library.
+
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=tests/va_list_shim.c");
+
+    //Only the va_list test needs the shim, it requires the test-util feature and a unix target.
+    if env::var_os("CARGO_FEATURE_TEST_UTIL").is_none() || env::var_os("CARGO_CFG_UNIX").is_none() {
+        return;
+    }
+
+    let output = PathBuf::from(env::var_os("OUT_DIR").expect("OUT_DIR not set")).join("libva_list_shim.so");
+    let status = cc::Build::new()
+        .cargo_metadata(false)
+        .get_compiler()
+        .to_command()
+        .args(["-shared", "-fPIC", "-o"])
+        .arg(&output)
+        .arg("tests/va_list_shim.c")
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "failed to compile tests/va_list_shim.c");
+
+    println!("cargo:rustc-env=JNI_SIMPLE_VA_LIST_SHIM={}", output.display());
+}
@@ -0,0 +1,50 @@
+//!
+//! Embeds a JVM into a rust program.
+//!
+//! The JVM is loaded from `JAVA_HOME`, the class `EmbedExample` is defined from the class file
+//! embedded into this binary and its `main` method is called with arguments created in rust.
+//!
+//! Run with `cargo run --example embed --features loadjvm`.
+//!
+
+use jni_simple::*;
+use std::ptr::null_mut;
+
+fn main() {
+    unsafe {
+        if let Err(err) = load_jvm_from_java_home() {
+            eprintln!("JVM not available, skipping: {err}");
+            return;
+        }
+
+        let (vm, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &Vec::new()).expect("failed to create JVM");
+
+        let class = env.DefineClass_from_slice("EmbedExample", null_mut(), include_bytes!("../java_testcode/EmbedExample.class"));
+        if class.is_null() {
+            env.ExceptionDescribe();
+            panic!("failed to define EmbedExample");
+        }
+
+        let main = env.GetStaticMethodID(class, "main", "([Ljava/lang/String;)V");
+        if main.is_null() {
+            env.ExceptionDescribe();
+            panic!("EmbedExample has no main method");
+        }
+
+        let args = env.new_string_array(&["hello", "from", "rust"]);
+        if args.is_null() {
+            env.ExceptionDescribe();
+            panic!("failed to create arguments");
+        }
+
+        env.CallStaticVoidMethod1(class, main, args);
+        if env.ExceptionCheck() {
+            env.ExceptionDescribe();
+            panic!("EmbedExample.main threw an exception");
+        }
+
+        env.DeleteLocalRef(args);
+        env.DeleteLocalRef(class);
+        println!("JVM destroyed: {:?}", vm.DestroyJavaVM());
+    }
+}
@@ -0,0 +1,84 @@
+//!
+//! A shared library that implements the native methods of the java class `NativeLibraryExample`.
+//!
+//! The natives are registered in `JNI_OnLoad` with `RegisterNatives`, so the rust functions do not need mangled names.
+//! They show how strings and arrays are passed between java and rust.
+//!
+//! Build with `cargo build --example native_library` and run with
+//! `java -cp java_testcode NativeLibraryExample target/debug/examples/libnative_library.so`.
+//!
+
+use jni_simple::*;
+use std::ffi::c_void;
+
+/// Throws a `NullPointerException` with the given message.
+unsafe fn throw_null_pointer(env: JNIEnv, message: &str) {
+    let class = env.FindClass("java/lang/NullPointerException");
+    if class.is_null() {
+        return;
+    }
+
+    env.ThrowNew(class, message);
+    env.DeleteLocalRef(class);
+}
+
+/// `static native String greet(String name)`
+unsafe fn greet(env: JNIEnv, _class: jclass, name: jstring) -> jstring {
+    let Some(name) = env.GetStringUTFChars_as_string(name) else {
+        throw_null_pointer(env, "name");
+        return std::ptr::null_mut();
+    };
+
+    env.NewStringUTF(format!("Hello {name} from rust"))
+}
+
+/// `static native int sum(int[] values)`
+unsafe fn sum(env: JNIEnv, _class: jclass, values: jintArray) -> jint {
+    if values.is_null() {
+        throw_null_pointer(env, "values");
+        return 0;
+    }
+
+    let mut buffer = vec![0; usize::try_from(env.GetArrayLength(values)).unwrap_or_default()];
+    env.GetIntArrayRegion_into_slice(values, 0, buffer.as_mut_slice());
+    buffer.iter().copied().fold(0, jint::wrapping_add)
+}
+
+/// `static native String[] split(String text)`
+unsafe fn split(env: JNIEnv, _class: jclass, text: jstring) -> jobjectArray {
+    let Some(text) = env.GetStringUTFChars_as_string(text) else {
+        throw_null_pointer(env, "text");
+        return std::ptr::null_mut();
+    };
+
+    env.new_string_array(text.split_whitespace().collect::<Vec<_>>().as_slice())
+}
+
+/// Registers the native methods of `NativeLibraryExample` when the library is loaded with `System.load`.
+///
+/// # Safety
+/// Must only be called by the JVM.
+#[no_mangle]
+pub unsafe extern "system" fn JNI_OnLoad(vm: JavaVM, _reserved: *mut c_void) -> jint {
+    let Ok(env) = vm.GetEnv(JNI_VERSION_1_8) else {
+        return JNI_ERR;
+    };
+
+    let class = env.FindClass("NativeLibraryExample");
+    if class.is_null() {
+        return JNI_ERR;
+    }
+
+    let methods = [
+        native_method!("greet", "(Ljava/lang/String;)Ljava/lang/String;", catch_unwind fn greet(env: JNIEnv, class: jclass, name: jstring) -> jstring),
+        native_method!("sum", "([I)I", catch_unwind fn sum(env: JNIEnv, class: jclass, values: jintArray) -> jint),
+        native_method!("split", "(Ljava/lang/String;)[Ljava/lang/String;", catch_unwind fn split(env: JNIEnv, class: jclass, text: jstring) -> jobjectArray),
+    ];
+    let result = env.RegisterNatives_from_slice(class, &methods);
+    env.DeleteLocalRef(class);
+    if result != JNI_OK {
+        return JNI_ERR;
+    }
+
+    JNI_VERSION_1_8
+}
@@ -0,0 +1,4 @@
+public class CallAndClearBench {
+    public static void noop() {
+    }
+}
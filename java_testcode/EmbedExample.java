@@ -0,0 +1,5 @@
+public class EmbedExample {
+    public static void main(String[] args) {
+        System.out.println("EmbedExample.main called with " + String.join(",", args));
+    }
+}
@@ -0,0 +1,11 @@
+public class FieldKindFixture {
+    public static int staticCount = 3;
+    public int count = 7;
+}
+
+class FieldKindFixtureSub extends FieldKindFixture {
+}
+
+class FieldKindFixtureOther {
+    public static int other = 5;
+}
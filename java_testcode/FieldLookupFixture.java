@@ -0,0 +1,14 @@
+public class FieldLookupFixture extends FieldLookupFixtureBase {
+    public int count = 7;
+    public long big = 1L << 40;
+    public boolean flag = true;
+    public String name = "fixture \u20AC";
+    public String missingName = null;
+    public Object nothing = null;
+    public Object something = "something";
+    public static int staticCount = 3;
+}
+
+class FieldLookupFixtureBase {
+    private int inherited = 11;
+}
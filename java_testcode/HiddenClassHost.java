@@ -0,0 +1,13 @@
+import java.lang.invoke.MethodHandles;
+
+public class HiddenClassHost {
+    public static MethodHandles.Lookup lookup() {
+        return MethodHandles.lookup();
+    }
+
+    public static native Class<?> defineNative();
+
+    public static Class<?> callDefineNative() {
+        return defineNative();
+    }
+}
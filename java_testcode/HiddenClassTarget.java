@@ -0,0 +1,5 @@
+public class HiddenClassTarget {
+    public static int answer() {
+        return 42;
+    }
+}
@@ -0,0 +1,14 @@
+public class NativeLibraryExample {
+    static native String greet(String name);
+
+    static native int sum(int[] values);
+
+    static native String[] split(String text);
+
+    public static void main(String[] args) {
+        System.load(args[0]);
+        System.out.println(greet("java"));
+        System.out.println(sum(new int[] {1, 2, 3, 4}));
+        System.out.println(String.join("|", split("a b c")));
+    }
+}
@@ -0,0 +1,32 @@
+public class NativeMethodTest {
+    private final String prefix;
+
+    public NativeMethodTest(String prefix) {
+        this.prefix = prefix;
+    }
+
+    public static native int add(int a, int b);
+
+    public native String greet(String name);
+
+    public native double scale(long value, float factor);
+
+    public static native void boom(String message);
+
+    public static int callAdd(int a, int b) {
+        return add(a, b);
+    }
+
+    public String callGreet(String name) {
+        return greet(name);
+    }
+
+    public static String callBoom(String message) {
+        try {
+            boom(message);
+            return "no exception";
+        } catch (RuntimeException e) {
+            return e.getMessage();
+        }
+    }
+}
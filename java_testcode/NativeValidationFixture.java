@@ -0,0 +1,16 @@
+public class NativeValidationFixture {
+
+    public static native int add(int a, int b);
+
+    public native String greet(String name);
+
+    public native long sum(long[] values);
+
+    public static int notNative(int a) {
+        return a;
+    }
+
+    public static String callGreet(String name) {
+        return new NativeValidationFixture().greet(name);
+    }
+}
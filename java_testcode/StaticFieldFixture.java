@@ -0,0 +1,21 @@
+public class StaticFieldFixture {
+    public static int port;
+    public static long budget;
+    public static boolean verbose;
+    public static String name;
+    public static java.util.List<String> tags;
+
+    public static String describe() {
+        return port + "," + budget + "," + verbose + "," + name + "," + tags;
+    }
+}
+
+class StaticFieldFixtureBroken {
+    public static int port;
+
+    static {
+        if (true) {
+            throw new IllegalStateException("broken");
+        }
+    }
+}
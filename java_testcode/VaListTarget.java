@@ -0,0 +1,15 @@
+public class VaListTarget {
+    private final int base;
+
+    public VaListTarget(int base, long extra) {
+        this.base = base + (int) extra;
+    }
+
+    public int add(int a, short b, byte c) {
+        return base + a + b + c;
+    }
+
+    public static String describe(int i, long l, float f, double d, boolean z, char c, String s) {
+        return i + "," + l + "," + f + "," + d + "," + z + "," + c + "," + s;
+    }
+}
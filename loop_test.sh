@@ -4,5 +4,5 @@ set -e
 while :
 do
 	cargo clean
-	cargo test --release --features loadjvm
+	cargo test --release --features loadjvm,test-util
 done
@@ -0,0 +1,295 @@
+//!
+//! Parser for the options string that is passed to `Agent_OnLoad` and `Agent_OnAttach`.
+//!
+//! The JVM passes everything after the `=` of `-agentpath:/path/to/agent.so=<options>` to the agent as a single string.
+//! By convention this string is a comma separated list of `key=value` pairs and flags without value,
+//! i.e. `logfile=/tmp/x,level=debug,sample`.
+//!
+//! # Example
+//! ```rust
+//! use jni_simple::agent_options;
+//!
+//! let options = agent_options::parse(Some("logfile=/tmp/x,level=debug,sample,interval=10"));
+//! assert_eq!(options.get("logfile"), Some("/tmp/x"));
+//! assert!(options.flag("sample"));
+//! assert_eq!(options.get_parsed::<u64>("interval"), Ok(Some(10)));
+//! ```
+//!
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+///
+/// Parses an agent options string.
+///
+/// * Entries are separated by `,`. Empty entries, such as the one after a trailing comma, are ignored.
+/// * An entry is split into key and value at the first `=`. An entry without `=` is a key without a value.
+/// * `\,` `\=` and `\\` escape a comma, an equals sign and a backslash. A backslash before any other character is kept as is.
+/// * Entries with an empty key are ignored.
+/// * If a key occurs more than once, the last value wins. The key keeps the position of its first occurrence.
+/// * Whitespace is not trimmed.
+///
+/// None and the empty string both produce empty options.
+///
+#[must_use]
+pub fn parse(options: Option<&str>) -> AgentOptions {
+    let mut result = AgentOptions::default();
+    let Some(options) = options else {
+        return result;
+    };
+
+    let mut key = String::new();
+    let mut value: Option<String> = None;
+    let mut chars = options.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ',' => {
+                result.insert(std::mem::take(&mut key), value.take());
+                continue;
+            }
+            '=' if value.is_none() => {
+                value = Some(String::new());
+                continue;
+            }
+            _ => {}
+        }
+
+        let target = value.as_mut().unwrap_or(&mut key);
+        if c != '\\' {
+            target.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some(escaped @ (',' | '=' | '\\')) => target.push(escaped),
+            Some(other) => {
+                target.push('\\');
+                target.push(other);
+            }
+            None => target.push('\\'),
+        }
+    }
+
+    result.insert(key, value);
+    result
+}
+
+///
+/// The parsed options of an agent.
+///
+/// Iteration yields the keys in the order in which they first occurred in the options string.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AgentOptions {
+    /// The keys and their values in order of first occurrence.
+    entries: Vec<(String, Option<String>)>,
+}
+
+impl AgentOptions {
+    /// Adds an entry, replacing the value of an existing entry with the same key.
+    fn insert(&mut self, key: String, value: Option<String>) {
+        if key.is_empty() {
+            return;
+        }
+
+        if let Some(entry) = self.entries.iter_mut().find(|(existing, _)| *existing == key) {
+            entry.1 = value;
+            return;
+        }
+
+        self.entries.push((key, value));
+    }
+
+    ///
+    /// Returns the value of a key.
+    ///
+    /// None if the key does not exist or has no value.
+    ///
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.iter().find(|(existing, _)| existing == key).and_then(|(_, value)| value.as_deref())
+    }
+
+    ///
+    /// Returns true if the key exists, with or without a value.
+    ///
+    #[must_use]
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.iter().any(|(existing, _)| existing == key)
+    }
+
+    ///
+    /// Returns true if the key exists without a value or if its value is `true`.
+    ///
+    #[must_use]
+    pub fn flag(&self, key: &str) -> bool {
+        self.entries
+            .iter()
+            .find(|(existing, _)| existing == key)
+            .is_some_and(|(_, value)| value.as_deref().unwrap_or("true") == "true")
+    }
+
+    ///
+    /// Parses the value of a key with `FromStr`.
+    ///
+    /// # Returns
+    /// None if the key does not exist or has no value.
+    ///
+    /// # Errors
+    /// If the value cannot be parsed.
+    ///
+    pub fn get_parsed<T: FromStr>(&self, key: &str) -> Result<Option<T>, ParseError>
+    where
+        T::Err: Display,
+    {
+        let Some(value) = self.get(key) else {
+            return Ok(None);
+        };
+
+        value.parse::<T>().map(Some).map_err(|err| ParseError {
+            key: key.to_string(),
+            value: value.to_string(),
+            message: err.to_string(),
+        })
+    }
+
+    ///
+    /// Returns an iterator over all keys and their values in order of first occurrence.
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Option<&str>)> {
+        self.entries.iter().map(|(key, value)| (key.as_str(), value.as_deref()))
+    }
+
+    ///
+    /// Returns the amount of distinct keys.
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    ///
+    /// Returns true if there are no keys.
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Error returned by `AgentOptions::get_parsed` if a value cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The key whose value could not be parsed.
+    key: String,
+    /// The value that could not be parsed.
+    value: String,
+    /// The message of the error returned by `FromStr`.
+    message: String,
+}
+
+impl ParseError {
+    /// The key whose value could not be parsed.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The value that could not be parsed.
+    #[must_use]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// The message of the error returned by `FromStr`.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("invalid value {:?} for agent option {}: {}", self.value, self.key, self.message))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+#[test]
+fn test_parse_empty() {
+    assert!(parse(None).is_empty());
+    assert!(parse(Some("")).is_empty());
+    assert!(parse(Some(",,,")).is_empty());
+    assert!(parse(Some("=value")).is_empty());
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse() {
+    let options = parse(Some("logfile=/tmp/x,level=debug,sample"));
+    assert_eq!(options.len(), 3);
+    assert_eq!(options.get("logfile"), Some("/tmp/x"));
+    assert_eq!(options.get("level"), Some("debug"));
+    assert_eq!(options.get("sample"), None);
+    assert!(options.contains("sample"));
+    assert!(options.flag("sample"));
+    assert!(!options.flag("level"));
+    assert!(!options.flag("missing"));
+    assert!(!options.contains("missing"));
+    assert_eq!(
+        options.iter().collect::<Vec<_>>(),
+        vec![("logfile", Some("/tmp/x")), ("level", Some("debug")), ("sample", None)]
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_malformed() {
+    let options = parse(Some("a=1,"));
+    assert_eq!(options.iter().collect::<Vec<_>>(), vec![("a", Some("1"))]);
+
+    let options = parse(Some(",a,,b=,c==d"));
+    assert_eq!(options.iter().collect::<Vec<_>>(), vec![("a", None), ("b", Some("")), ("c", Some("=d"))]);
+    assert!(!options.flag("b"));
+
+    let options = parse(Some("a=1,b=2,a=3,a"));
+    assert_eq!(options.iter().collect::<Vec<_>>(), vec![("a", None), ("b", Some("2"))]);
+
+    let options = parse(Some("a=1,b,a=3"));
+    assert_eq!(options.iter().collect::<Vec<_>>(), vec![("a", Some("3")), ("b", None)]);
+
+    let options = parse(Some("enabled=true,disabled=false"));
+    assert!(options.flag("enabled"));
+    assert!(!options.flag("disabled"));
+
+    let options = parse(Some(" a = 1 "));
+    assert_eq!(options.get(" a "), Some(" 1 "));
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_escapes() {
+    let options = parse(Some(r"path=/tmp/a\,b,key\=name=x\\y,other=\n,end=\"));
+    assert_eq!(
+        options.iter().collect::<Vec<_>>(),
+        vec![("path", Some("/tmp/a,b")), ("key=name", Some(r"x\y")), ("other", Some(r"\n")), ("end", Some(r"\"))]
+    );
+}
+
+#[cfg(test)]
+#[test]
+fn test_get_parsed() {
+    let options = parse(Some("interval=10,ratio=0.5,bad=ten,flag"));
+    assert_eq!(options.get_parsed::<u64>("interval"), Ok(Some(10)));
+    assert_eq!(options.get_parsed::<f64>("ratio"), Ok(Some(0.5)));
+    assert_eq!(options.get_parsed::<u64>("missing"), Ok(None));
+    assert_eq!(options.get_parsed::<u64>("flag"), Ok(None));
+
+    let err = options.get_parsed::<u64>("bad").expect_err("ten is not a number");
+    assert_eq!(err.key(), "bad");
+    assert_eq!(err.value(), "ten");
+    assert_eq!(err.message(), "invalid digit found in string");
+    assert_eq!(err.to_string(), "invalid value \"ten\" for agent option bad: invalid digit found in string");
+}
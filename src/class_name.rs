@@ -0,0 +1,148 @@
+//!
+//! Conversion and validation of java class names.
+//!
+//! Java uses several spellings for the same class:
+//! * The binary name returned by `Class#getName()`, i.e. `java.lang.String`, `java.util.Map$Entry` or `[Ljava.lang.String;` for arrays.
+//! * The internal name used by `FindClass` and `DefineClass`, i.e. `java/lang/String` or `[Ljava/lang/String;` for arrays.
+//! * The field descriptor used in signatures, i.e. `Ljava/lang/String;` or `[Ljava/lang/String;` for arrays.
+//!
+//! # Example
+//! ```rust
+//! use jni_simple::class_name;
+//!
+//! assert_eq!(class_name::to_internal("java.util.Map$Entry"), "java/util/Map$Entry");
+//! assert_eq!(class_name::to_internal("Ljava/lang/String;"), "java/lang/String");
+//! assert_eq!(class_name::to_binary("[Ljava/lang/String;"), "[Ljava.lang.String;");
+//! assert!(class_name::validate("java.lang.String").is_err());
+//! ```
+//!
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+///
+/// Common mistakes in class names passed to `FindClass` or `DefineClass`, returned by `validate`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassNameError {
+    /// The name is empty.
+    Empty,
+    /// The name contains a '.', which is the separator of binary names.
+    ContainsDot,
+    /// The name is a field descriptor such as `Ljava/lang/String;`.
+    FieldDescriptor,
+    /// The name is an array type that is not in descriptor form, such as `java/lang/String[]`.
+    ArrayNotDescriptor,
+    /// The name contains a NUL character.
+    ContainsNul,
+    /// The name contains whitespace.
+    ContainsWhitespace,
+}
+
+impl Display for ClassNameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("is empty"),
+            Self::ContainsDot => f.write_str("contains '.', did you mean '/'"),
+            Self::FieldDescriptor => f.write_str("looks like a field descriptor (Lxxx;), pass the plain internal name"),
+            Self::ArrayNotDescriptor => f.write_str("array classes must use descriptor form like [Ljava/lang/String;"),
+            Self::ContainsNul => f.write_str("contains a NUL character"),
+            Self::ContainsWhitespace => f.write_str("contains whitespace"),
+        }
+    }
+}
+
+impl std::error::Error for ClassNameError {}
+
+///
+/// Checks if `name` is a valid internal class name as expected by `FindClass` and `DefineClass`.
+///
+/// This only detects common mistakes, a name that passes is not guaranteed to be accepted by the JVM.
+/// The asserts feature panics with the message of the error if `FindClass` or `DefineClass` is called with such a name.
+///
+/// # Errors
+/// The first mistake found in `name`.
+///
+pub fn validate(name: &str) -> Result<(), ClassNameError> {
+    if name.is_empty() {
+        return Err(ClassNameError::Empty);
+    }
+
+    if name.contains('\0') {
+        return Err(ClassNameError::ContainsNul);
+    }
+
+    if name.chars().any(char::is_whitespace) {
+        return Err(ClassNameError::ContainsWhitespace);
+    }
+
+    if let Some(component) = name.strip_prefix('[') {
+        let component = component.trim_start_matches('[');
+        let valid = match component.as_bytes() {
+            [b'Z' | b'B' | b'S' | b'C' | b'I' | b'J' | b'F' | b'D'] => true,
+            [b'L', .., b';'] => component.len() > 2,
+            _ => false,
+        };
+        if !valid {
+            return Err(ClassNameError::ArrayNotDescriptor);
+        }
+    } else if name.contains('[') || name.contains(']') {
+        return Err(ClassNameError::ArrayNotDescriptor);
+    } else if name.len() > 2 && name.starts_with('L') && name.ends_with(';') {
+        return Err(ClassNameError::FieldDescriptor);
+    }
+
+    if name.contains('.') {
+        return Err(ClassNameError::ContainsDot);
+    }
+
+    Ok(())
+}
+
+///
+/// Converts a binary class name or a field descriptor to the internal name expected by `FindClass`.
+///
+/// * `java.lang.String` -> `java/lang/String`
+/// * `Ljava/lang/String;` -> `java/lang/String`
+/// * `[Ljava.lang.String;` -> `[Ljava/lang/String;`
+///
+/// Names that already are internal names are returned unchanged.
+///
+#[must_use]
+pub fn to_internal(name: &str) -> Cow<'_, str> {
+    let name = if name.starts_with('[') {
+        name
+    } else {
+        name.strip_prefix('L').and_then(|name| name.strip_suffix(';')).unwrap_or(name)
+    };
+
+    if name.contains('.') {
+        return Cow::Owned(name.replace('.', "/"));
+    }
+
+    Cow::Borrowed(name)
+}
+
+///
+/// Converts an internal class name or a field descriptor to the binary name returned by `Class#getName()`.
+///
+/// * `java/lang/String` -> `java.lang.String`
+/// * `Ljava/lang/String;` -> `java.lang.String`
+/// * `[Ljava/lang/String;` -> `[Ljava.lang.String;`
+///
+/// Names that already are binary names are returned unchanged.
+///
+#[must_use]
+pub fn to_binary(name: &str) -> Cow<'_, str> {
+    let name = if name.starts_with('[') {
+        name
+    } else {
+        name.strip_prefix('L').and_then(|name| name.strip_suffix(';')).unwrap_or(name)
+    };
+
+    if name.contains('/') {
+        return Cow::Owned(name.replace('/', "."));
+    }
+
+    Cow::Borrowed(name)
+}
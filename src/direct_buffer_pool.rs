@@ -0,0 +1,303 @@
+//!
+//! Pool of reusable nio direct `ByteBuffer`s backed by rust memory.
+//!
+
+use crate::{jlong, jmethodID, jobject, JNIEnv};
+use once_cell::sync::OnceCell;
+use std::ffi::c_void;
+use std::mem::ManuallyDrop;
+use std::sync::{Mutex, PoisonError};
+use sync_ptr::SyncMutPtr;
+
+///
+/// Memory and the global reference of the direct `ByteBuffer` that wraps it.
+///
+#[derive(Debug)]
+struct DirectBuffer {
+    /// The memory the `ByteBuffer` points to. Its heap allocation never moves.
+    memory: Box<[u8]>,
+    /// Global reference to the `ByteBuffer`.
+    buffer: SyncMutPtr<c_void>,
+}
+
+///
+/// Pool of direct `ByteBuffer`s that are backed by memory owned by rust.
+///
+/// Passing large payloads to java through a `byte[]` requires copying them with `SetByteArrayRegion`.
+/// A direct `ByteBuffer` lets rust write into memory that java can read without a copy,
+/// but creating one per payload is expensive. This pool keeps both the memory and the `ByteBuffer` object
+/// and hands them out again once a `PooledBuffer` is dropped.
+///
+/// The memory of every buffer is kept alive for as long as the pool holds a global reference to its `ByteBuffer`.
+/// The pool does not delete those global references when it is dropped, because it has no `JNIEnv` to do so,
+/// instead the memory of all buffers still in the pool is leaked so that the `ByteBuffer`s never dangle.
+/// Call `destroy` before dropping it if the buffers should be freed.
+///
+/// The capacity of a direct `ByteBuffer` is a jlong, so buffers larger than `jlong::MAX` bytes cannot be created.
+///
+/// # Example
+/// ```rust
+/// use jni_simple::*;
+/// use once_cell::sync::Lazy;
+///
+/// static BUFFERS: Lazy<DirectBufferPool> = Lazy::new(DirectBufferPool::new);
+///
+/// unsafe fn send(env: JNIEnv, receiver: jobject, accept: jmethodID, payload: &[u8]) {
+///     let Some(mut buffer) = BUFFERS.acquire(&env, payload.len()) else {
+///         return; //Exception is pending
+///     };
+///     buffer.as_mut_slice()[..payload.len()].copy_from_slice(payload);
+///     env.CallVoidMethod2(receiver, accept, buffer.java_buffer(), payload.len() as jint);
+///     //buffer is returned to the pool here, java must not keep a reference to it.
+/// }
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct DirectBufferPool {
+    /// Buffers that are currently not in use.
+    free: Mutex<Vec<DirectBuffer>>,
+    /// The `Buffer.clear()` method, resolved when the first buffer is reused.
+    clear: OnceCell<SyncMutPtr<c_void>>,
+}
+
+///
+/// A direct `ByteBuffer` borrowed from a `DirectBufferPool`.
+///
+/// The buffer is returned to the pool when this is dropped.
+///
+#[derive(Debug)]
+pub struct PooledBuffer<'a> {
+    /// The pool to return the buffer to.
+    pool: &'a DirectBufferPool,
+    /// The buffer, moved back into the pool on drop.
+    buffer: ManuallyDrop<DirectBuffer>,
+}
+
+impl DirectBufferPool {
+    ///
+    /// Creates a new empty pool.
+    ///
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Borrows a buffer with at least `min_capacity` bytes from the pool.
+    ///
+    /// The smallest buffer in the pool that is large enough is reused.
+    /// If there is none, new memory is allocated and a new `ByteBuffer` is created for it.
+    /// A reused `ByteBuffer` is cleared, so its position is 0 and its limit is its capacity.
+    /// The contents of the memory of a reused buffer are whatever was written to it last.
+    ///
+    /// # Returns
+    /// The borrowed buffer or None if an exception was thrown.
+    ///
+    /// # Throws Java Exception:
+    /// * `OutOfMemoryError` - if the system runs out of memory.
+    ///
+    /// # Panics
+    /// if `min_capacity` is larger than `jlong::MAX`.
+    /// if asserts feature is enabled and UB was detected
+    ///
+    /// # Safety
+    ///
+    /// Current thread must not be detached from JNI.
+    ///
+    /// Current thread does not hold a critical reference.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/functions.html#GetPrimitiveArrayCritical_ReleasePrimitiveArrayCritical>
+    ///
+    /// Current thread is not currently throwing a Java exception.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/design.html#java_exceptions>
+    ///
+    /// All calls to this pool must be made with an env of the same JVM.
+    ///
+    pub unsafe fn acquire(&self, env: &JNIEnv, min_capacity: usize) -> Option<PooledBuffer<'_>> {
+        let capacity = min_capacity.max(1);
+        let capacity_jlong = jlong::try_from(capacity).expect("capacity of a direct ByteBuffer must fit into a jlong");
+
+        let reused = {
+            let mut free = self.free.lock().unwrap_or_else(PoisonError::into_inner);
+            free.iter()
+                .enumerate()
+                .filter(|(_, buffer)| buffer.memory.len() >= capacity)
+                .min_by_key(|(_, buffer)| buffer.memory.len())
+                .map(|(index, _)| index)
+                .map(|index| free.swap_remove(index))
+        };
+
+        if let Some(buffer) = reused {
+            let pooled = PooledBuffer {
+                pool: self,
+                buffer: ManuallyDrop::new(buffer),
+            };
+            if !self.clear(env, pooled.java_buffer()) {
+                return None;
+            }
+
+            return Some(pooled);
+        }
+
+        let mut memory = vec![0u8; capacity].into_boxed_slice();
+        let local = env.NewDirectByteBuffer(memory.as_mut_ptr().cast(), capacity_jlong);
+        if local.is_null() {
+            return None;
+        }
+
+        let global = env.NewGlobalRef(local);
+        env.DeleteLocalRef(local);
+        if global.is_null() {
+            return None;
+        }
+
+        Some(PooledBuffer {
+            pool: self,
+            buffer: ManuallyDrop::new(DirectBuffer {
+                memory,
+                buffer: SyncMutPtr::new(global),
+            }),
+        })
+    }
+
+    ///
+    /// Calls `Buffer.clear()` on the `ByteBuffer`.
+    /// Returns false if an exception was thrown.
+    ///
+    unsafe fn clear(&self, env: &JNIEnv, buffer: jobject) -> bool {
+        let Some(clear) = self.clear_method(env) else {
+            return false;
+        };
+
+        let result = env.CallObjectMethod0(buffer, clear);
+        if env.ExceptionCheck() {
+            return false;
+        }
+
+        env.DeleteLocalRef(result);
+        true
+    }
+
+    ///
+    /// Returns the cached `Buffer.clear()` method, resolving it on first use.
+    /// Returns None if an exception was thrown.
+    ///
+    unsafe fn clear_method(&self, env: &JNIEnv) -> Option<jmethodID> {
+        if let Some(clear) = self.clear.get() {
+            return Some(clear.inner());
+        }
+
+        let class = env.FindClass("java/nio/Buffer");
+        if class.is_null() {
+            return None;
+        }
+
+        let clear = env.GetMethodID(class, "clear", "()Ljava/nio/Buffer;");
+        env.DeleteLocalRef(class);
+        if clear.is_null() {
+            return None;
+        }
+
+        //Another thread may have resolved it concurrently, the method id is the same either way.
+        Some(self.clear.get_or_init(|| SyncMutPtr::new(clear)).inner())
+    }
+
+    ///
+    /// Returns the amount of buffers that are currently in the pool and not borrowed.
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.free.lock().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    ///
+    /// Returns true if no buffers are currently in the pool.
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Deletes the global references of all buffers that are currently in the pool and frees their memory.
+    ///
+    /// Buffers that are borrowed while this is called are returned to the pool once they are dropped.
+    ///
+    /// # Panics
+    /// if asserts feature is enabled and UB was detected
+    ///
+    /// # Safety
+    ///
+    /// Current thread must not be detached from JNI.
+    ///
+    /// Current thread does not hold a critical reference.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/functions.html#GetPrimitiveArrayCritical_ReleasePrimitiveArrayCritical>
+    ///
+    /// Java must not use any of the destroyed `ByteBuffer`s after this call,
+    /// not even through references it obtained before this call.
+    ///
+    pub unsafe fn destroy(&self, env: &JNIEnv) {
+        let free = std::mem::take(&mut *self.free.lock().unwrap_or_else(PoisonError::into_inner));
+        for buffer in free {
+            env.DeleteGlobalRef(buffer.buffer.inner());
+            drop(buffer.memory);
+        }
+    }
+}
+
+impl Drop for DirectBufferPool {
+    fn drop(&mut self) {
+        let free = std::mem::take(&mut *self.free.lock().unwrap_or_else(PoisonError::into_inner));
+        for buffer in free {
+            //The ByteBuffer may still be alive in java, so its memory must never be freed.
+            Box::leak(buffer.memory);
+        }
+    }
+}
+
+impl PooledBuffer<'_> {
+    ///
+    /// Returns the capacity of the buffer in bytes.
+    /// This is at least the capacity that was requested from the pool.
+    ///
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.buffer.memory.len()
+    }
+
+    ///
+    /// Returns the memory of the buffer.
+    ///
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buffer.memory
+    }
+
+    ///
+    /// Returns the memory of the buffer for writing.
+    ///
+    /// Java must not access the buffer while the returned slice is in use.
+    ///
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.buffer.memory
+    }
+
+    ///
+    /// Returns a global reference to the direct `ByteBuffer` that is backed by the memory of this buffer.
+    ///
+    /// The reference is owned by the pool and must not be deleted by the caller.
+    /// Java must not use the `ByteBuffer` after this `PooledBuffer` is dropped,
+    /// because the pool will hand it out again.
+    ///
+    #[must_use]
+    pub fn java_buffer(&self) -> jobject {
+        self.buffer.buffer.inner()
+    }
+}
+
+impl Drop for PooledBuffer<'_> {
+    fn drop(&mut self) {
+        //SAFETY: self.buffer is not used after this.
+        let buffer = unsafe { ManuallyDrop::take(&mut self.buffer) };
+        self.pool.free.lock().unwrap_or_else(PoisonError::into_inner).push(buffer);
+    }
+}
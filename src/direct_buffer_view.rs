@@ -0,0 +1,122 @@
+//!
+//! View of the memory of a direct nio buffer that takes position and limit into account.
+//!
+
+///
+/// The memory of a direct `java.nio.ByteBuffer` together with its position and limit at the time the view was created.
+///
+/// Created by `JNIEnv::direct_buffer_view`.
+/// `GetDirectBufferAddress` and `GetDirectBufferCapacity` always describe the entire buffer,
+/// while java code usually only wants the elements between position and limit to be read, for example after calling `flip()`.
+///
+/// Positions and lengths are counted in bytes, views of other element types such as `asIntBuffer()` are not supported.
+/// The view does not keep the buffer alive and does not see later changes of its position or limit.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct DirectBufferView {
+    /// Address of the first element of the buffer.
+    address: *mut u8,
+    /// Capacity of the buffer.
+    capacity: usize,
+    /// Position of the buffer.
+    position: usize,
+    /// Limit of the buffer.
+    limit: usize,
+    /// True if the buffer is read only.
+    read_only: bool,
+}
+
+impl DirectBufferView {
+    /// Creates a view, position and limit must not exceed capacity.
+    pub(crate) const fn new(address: *mut u8, capacity: usize, position: usize, limit: usize, read_only: bool) -> Self {
+        Self {
+            address,
+            capacity,
+            position,
+            limit,
+            read_only,
+        }
+    }
+
+    ///
+    /// Returns the address of the first element of the buffer, ignoring its position.
+    ///
+    #[must_use]
+    pub const fn full_slice_ptr(&self) -> *const u8 {
+        self.address
+    }
+
+    ///
+    /// Returns the address of the first element of the buffer, ignoring its position.
+    /// None if the buffer is read only.
+    ///
+    #[must_use]
+    pub const fn full_slice_mut_ptr(&self) -> Option<*mut u8> {
+        if self.read_only {
+            return None;
+        }
+
+        Some(self.address)
+    }
+
+    ///
+    /// Returns the capacity of the buffer.
+    ///
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    ///
+    /// Returns the address of the element at the position of the buffer.
+    ///
+    #[must_use]
+    pub const fn remaining_slice_ptr(&self) -> *const u8 {
+        self.address.wrapping_add(self.position)
+    }
+
+    ///
+    /// Returns the address of the element at the position of the buffer.
+    /// None if the buffer is read only.
+    ///
+    #[must_use]
+    pub const fn remaining_slice_mut_ptr(&self) -> Option<*mut u8> {
+        if self.read_only {
+            return None;
+        }
+
+        Some(self.address.wrapping_add(self.position))
+    }
+
+    ///
+    /// Returns the amount of elements between the position and the limit of the buffer.
+    ///
+    #[must_use]
+    pub const fn remaining_len(&self) -> usize {
+        self.limit - self.position
+    }
+
+    ///
+    /// Returns the position of the buffer.
+    ///
+    #[must_use]
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    ///
+    /// Returns the limit of the buffer.
+    ///
+    #[must_use]
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    ///
+    /// Returns true if the buffer is read only. The mut pointer accessors return None in this case.
+    ///
+    #[must_use]
+    pub const fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+}
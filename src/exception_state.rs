@@ -0,0 +1,147 @@
+//!
+//! Typestate wrappers around `JNIEnv` that encode whether a java exception is pending.
+//!
+
+use crate::{
+    jarray, jboolean, jbooleanArray, jbyte, jbyteArray, jchar, jcharArray, jdouble, jdoubleArray, jfloat, jfloatArray, jint, jintArray, jlong, jlongArray, jobject, jshort,
+    jshortArray, jstring, jthrowable, jweak, JNIEnv,
+};
+use std::ffi::{c_char, c_void};
+use std::ops::Deref;
+
+///
+/// Result of `JNIEnv::check_pending`.
+///
+/// # Example
+/// ```rust
+/// use jni_simple::*;
+///
+/// unsafe fn call_listener(env: JNIEnv, listener: jobject, method: jmethodID, array: jintArray) {
+///     let elements = env.GetIntArrayElements(array, std::ptr::null_mut());
+///     env.CallVoidMethod1(listener, method, array);
+///     match env.check_pending() {
+///         EnvState::Clear(env) => env.ReleaseIntArrayElements(array, elements, JNI_COMMIT),
+///         EnvState::Pending(env, throwable) => {
+///             //Only the functions that are permitted while an exception is pending can be called here.
+///             env.ReleaseIntArrayElements(array, elements, JNI_ABORT);
+///             env.DeleteLocalRef(throwable);
+///             //env.CallVoidMethod0(listener, method); would not compile.
+///         }
+///     }
+/// }
+/// ```
+///
+#[derive(Debug)]
+pub enum EnvState {
+    /// No exception is pending.
+    Clear(ClearEnv),
+    /// An exception is pending. Contains a local reference to the exception, which the caller should delete.
+    Pending(PendingEnv, jthrowable),
+}
+
+///
+/// A `JNIEnv` that had no pending exception when it was created by `JNIEnv::check_pending` or `PendingEnv::ExceptionClear`.
+///
+/// Derefs to `JNIEnv`, so the full API is available.
+/// This does not prevent calls made through it from throwing new exceptions.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct ClearEnv(pub(crate) JNIEnv);
+
+impl Deref for ClearEnv {
+    type Target = JNIEnv;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+///
+/// A `JNIEnv` with a pending exception.
+///
+/// It only offers the functions that the JNI specification permits while an exception is pending.
+/// <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/design.html#exception_handling>
+///
+/// ```compile_fail
+/// use jni_simple::*;
+///
+/// unsafe fn call(env: PendingEnv, obj: jobject, method: jmethodID) {
+///     env.CallVoidMethod0(obj, method);
+/// }
+/// ```
+///
+#[derive(Debug)]
+pub struct PendingEnv(pub(crate) JNIEnv);
+
+/// Generates methods of `PendingEnv` that delegate to the `JNIEnv` method with the same name.
+macro_rules! delegate_pending {
+    ($(fn $name:ident(&self $(, $arg:ident: $ty:ty)*) $(-> $ret:ty)?;)*) => {
+        $(
+            #[doc = concat!("Calls `JNIEnv::", stringify!($name), "`.")]
+            ///
+            /// # Panics
+            /// if asserts feature is enabled and UB was detected
+            ///
+            /// # Safety
+            #[doc = concat!("Same as `JNIEnv::", stringify!($name), "`.")]
+            pub unsafe fn $name(&self $(, $arg: $ty)*) $(-> $ret)? {
+                self.0.$name($($arg),*)
+            }
+        )*
+    };
+}
+
+impl PendingEnv {
+    ///
+    /// Clears the pending exception.
+    ///
+    /// # Returns
+    /// The `JNIEnv` which no longer has a pending exception.
+    ///
+    /// # Safety
+    /// Same as `JNIEnv::ExceptionClear`.
+    ///
+    #[must_use]
+    pub unsafe fn ExceptionClear(self) -> ClearEnv {
+        self.0.ExceptionClear();
+        ClearEnv(self.0)
+    }
+
+    ///
+    /// Prints the pending exception and its stack trace to stderr. This clears the exception.
+    ///
+    /// # Returns
+    /// The `JNIEnv` which no longer has a pending exception.
+    ///
+    /// # Safety
+    /// Same as `JNIEnv::ExceptionDescribe`.
+    ///
+    #[must_use]
+    pub unsafe fn ExceptionDescribe(self) -> ClearEnv {
+        self.0.ExceptionDescribe();
+        ClearEnv(self.0)
+    }
+
+    delegate_pending! {
+        fn ExceptionOccurred(&self) -> jthrowable;
+        fn ExceptionCheck(&self) -> jboolean;
+        fn DeleteLocalRef(&self, obj: jobject);
+        fn DeleteGlobalRef(&self, obj: jobject);
+        fn DeleteWeakGlobalRef(&self, obj: jweak);
+        fn PushLocalFrame(&self, capacity: jint) -> jint;
+        fn PopLocalFrame(&self, result: jobject) -> jobject;
+        fn MonitorExit(&self, obj: jobject) -> jint;
+        fn ReleaseStringChars(&self, string: jstring, chars: *const jchar);
+        fn ReleaseStringUTFChars(&self, string: jstring, utf: *const c_char);
+        fn ReleaseStringCritical(&self, string: jstring, cstring: *const jchar);
+        fn ReleaseBooleanArrayElements(&self, array: jbooleanArray, elems: *mut jboolean, mode: jint);
+        fn ReleaseByteArrayElements(&self, array: jbyteArray, elems: *mut jbyte, mode: jint);
+        fn ReleaseCharArrayElements(&self, array: jcharArray, elems: *mut jchar, mode: jint);
+        fn ReleaseShortArrayElements(&self, array: jshortArray, elems: *mut jshort, mode: jint);
+        fn ReleaseIntArrayElements(&self, array: jintArray, elems: *mut jint, mode: jint);
+        fn ReleaseLongArrayElements(&self, array: jlongArray, elems: *mut jlong, mode: jint);
+        fn ReleaseFloatArrayElements(&self, array: jfloatArray, elems: *mut jfloat, mode: jint);
+        fn ReleaseDoubleArrayElements(&self, array: jdoubleArray, elems: *mut jdouble, mode: jint);
+        fn ReleasePrimitiveArrayCritical(&self, array: jarray, carray: *mut c_void, mode: jint);
+    }
+}
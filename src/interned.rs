@@ -0,0 +1,168 @@
+//!
+//! Cache of interned java strings.
+//!
+
+use crate::{jsize, jstring, JNIEnv};
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::ptr::null_mut;
+use std::sync::{PoisonError, RwLock};
+use sync_ptr::SyncMutPtr;
+
+///
+/// Cache that maps rust strings to global references of the equivalent interned java string.
+///
+/// Creating a new jstring for the same few constants over and over is expensive.
+/// The first call to `get` for a given string creates the java string, interns it by calling `String.intern()`
+/// and keeps a global reference to it. All later calls for the same string return that global reference.
+///
+/// The cache does not delete the global references when it is dropped, because it has no `JNIEnv` to do so.
+/// Call `clear` before dropping it if the references should be freed.
+///
+/// # Example
+/// ```rust
+/// use jni_simple::*;
+/// use once_cell::sync::Lazy;
+///
+/// static STRINGS: Lazy<InternedStrings> = Lazy::new(InternedStrings::new);
+///
+/// unsafe fn print(env: JNIEnv, stream: jobject, println: jmethodID) {
+///     let hello = STRINGS.get(&env, "Hello World");
+///     if hello.is_null() {
+///         return; //Exception is pending
+///     }
+///     env.CallVoidMethod1(stream, println, hello);
+///     //hello must not be deleted.
+/// }
+/// ```
+///
+#[derive(Debug, Default)]
+pub struct InternedStrings {
+    /// Global references to the interned strings keyed by their rust equivalent.
+    strings: RwLock<HashMap<String, SyncMutPtr<c_void>>>,
+}
+
+impl InternedStrings {
+    ///
+    /// Creates a new empty cache.
+    ///
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Returns a global reference to the interned java string that is equal to `s`.
+    ///
+    /// The string is created from the UTF-16 representation of `s`,
+    /// so strings that contain 0 bytes or characters outside the BMP are converted correctly.
+    ///
+    /// # Returns
+    /// A global reference to the interned string or null if an exception was thrown.
+    /// The returned reference is owned by the cache and must not be deleted by the caller.
+    /// It stays valid until `clear` is called.
+    ///
+    /// # Throws Java Exception:
+    /// * `OutOfMemoryError` - if the system runs out of memory.
+    ///
+    /// # Panics
+    /// if `s` has more than `jsize::MAX` UTF-16 characters.
+    /// if asserts feature is enabled and UB was detected
+    ///
+    /// # Safety
+    ///
+    /// Current thread must not be detached from JNI.
+    ///
+    /// Current thread does not hold a critical reference.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/functions.html#GetPrimitiveArrayCritical_ReleasePrimitiveArrayCritical>
+    ///
+    /// Current thread is not currently throwing a Java exception.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/design.html#java_exceptions>
+    ///
+    /// All calls to this cache must be made with an env of the same JVM.
+    ///
+    pub unsafe fn get(&self, env: &JNIEnv, s: &str) -> jstring {
+        if let Some(string) = self.strings.read().unwrap_or_else(PoisonError::into_inner).get(s) {
+            return string.inner();
+        }
+
+        let chars = s.encode_utf16().collect::<Vec<u16>>();
+        let len = jsize::try_from(chars.len()).expect("string is too long to be converted to a java string");
+        let local = env.NewString(chars.as_ptr(), len);
+        if local.is_null() {
+            return null_mut();
+        }
+
+        let string_class = env.GetObjectClass(local);
+        let intern = env.GetMethodID(string_class, "intern", "()Ljava/lang/String;");
+        env.DeleteLocalRef(string_class);
+        if intern.is_null() {
+            env.DeleteLocalRef(local);
+            return null_mut();
+        }
+
+        let interned = env.CallObjectMethod0(local, intern);
+        env.DeleteLocalRef(local);
+        if env.ExceptionCheck() || interned.is_null() {
+            return null_mut();
+        }
+
+        let global = env.NewGlobalRef(interned);
+        env.DeleteLocalRef(interned);
+        if global.is_null() {
+            return null_mut();
+        }
+
+        let mut guard = self.strings.write().unwrap_or_else(PoisonError::into_inner);
+        if let Some(existing) = guard.get(s) {
+            //Another thread was faster.
+            let existing = existing.inner();
+            drop(guard);
+            env.DeleteGlobalRef(global);
+            return existing;
+        }
+
+        guard.insert(s.to_string(), SyncMutPtr::new(global));
+        global
+    }
+
+    ///
+    /// Returns the amount of strings in the cache.
+    ///
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.strings.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    ///
+    /// Returns true if the cache contains no strings.
+    ///
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Deletes the global references of all cached strings and empties the cache.
+    ///
+    /// All references previously returned by `get` become invalid.
+    ///
+    /// # Panics
+    /// if asserts feature is enabled and UB was detected
+    ///
+    /// # Safety
+    ///
+    /// Current thread must not be detached from JNI.
+    ///
+    /// Current thread does not hold a critical reference.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/functions.html#GetPrimitiveArrayCritical_ReleasePrimitiveArrayCritical>
+    ///
+    /// No reference previously returned by `get` may be used after this call.
+    ///
+    pub unsafe fn clear(&self, env: &JNIEnv) {
+        let strings = std::mem::take(&mut *self.strings.write().unwrap_or_else(PoisonError::into_inner));
+        for string in strings.into_values() {
+            env.DeleteGlobalRef(string.inner());
+        }
+    }
+}
@@ -0,0 +1,394 @@
+//!
+//! Call counters and timings of the JNI functions, enabled by the `metrics` feature.
+//!
+//! Every call made through a `JNIEnv` or `JavaVM` increments the counter of the called function
+//! and adds the time spent in it. The counters are global and shared by all threads.
+//!
+//! Times are inclusive. If a JNI call runs java code that calls a native method which makes further JNI calls,
+//! the time of those calls is counted for them and again for the outer call.
+//! The times also include the work done by this crate in the same statement as the call,
+//! which is usually nothing but includes the checks of the `asserts` feature if it is enabled.
+//! Calls made by the checks of the `asserts` feature are counted as well.
+//!
+//! # Example
+//! ```rust
+//! use jni_simple::*;
+//!
+//! unsafe fn print_hot_functions(env: JNIEnv) {
+//!     metrics::reset();
+//!     //... do the work that should be measured with env
+//!     let mut snapshot = metrics::snapshot();
+//!     snapshot.sort_by_key(|(_, _, total_ns)| std::cmp::Reverse(*total_ns));
+//!     for (name, count, total_ns) in snapshot.iter().take(10) {
+//!         println!("{name}: {count} calls {total_ns}ns");
+//!     }
+//! }
+//! ```
+//!
+
+use std::ops::Deref;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Names of the functions in the `JNIEnv` function table, indexed by their position in the table.
+const ENV_NAMES: [&str; 235] = [
+    "reserved",
+    "reserved",
+    "reserved",
+    "reserved",
+    "GetVersion",
+    "DefineClass",
+    "FindClass",
+    "FromReflectedMethod",
+    "FromReflectedField",
+    "ToReflectedMethod",
+    "GetSuperclass",
+    "IsAssignableFrom",
+    "ToReflectedField",
+    "Throw",
+    "ThrowNew",
+    "ExceptionOccurred",
+    "ExceptionDescribe",
+    "ExceptionClear",
+    "FatalError",
+    "PushLocalFrame",
+    "PopLocalFrame",
+    "NewGlobalRef",
+    "DeleteGlobalRef",
+    "DeleteLocalRef",
+    "IsSameObject",
+    "NewLocalRef",
+    "EnsureLocalCapacity",
+    "AllocObject",
+    "NewObject",
+    "NewObjectV",
+    "NewObjectA",
+    "GetObjectClass",
+    "IsInstanceOf",
+    "GetMethodID",
+    "CallObjectMethod",
+    "CallObjectMethodV",
+    "CallObjectMethodA",
+    "CallBooleanMethod",
+    "CallBooleanMethodV",
+    "CallBooleanMethodA",
+    "CallByteMethod",
+    "CallByteMethodV",
+    "CallByteMethodA",
+    "CallCharMethod",
+    "CallCharMethodV",
+    "CallCharMethodA",
+    "CallShortMethod",
+    "CallShortMethodV",
+    "CallShortMethodA",
+    "CallIntMethod",
+    "CallIntMethodV",
+    "CallIntMethodA",
+    "CallLongMethod",
+    "CallLongMethodV",
+    "CallLongMethodA",
+    "CallFloatMethod",
+    "CallFloatMethodV",
+    "CallFloatMethodA",
+    "CallDoubleMethod",
+    "CallDoubleMethodV",
+    "CallDoubleMethodA",
+    "CallVoidMethod",
+    "CallVoidMethodV",
+    "CallVoidMethodA",
+    "CallNonvirtualObjectMethod",
+    "CallNonvirtualObjectMethodV",
+    "CallNonvirtualObjectMethodA",
+    "CallNonvirtualBooleanMethod",
+    "CallNonvirtualBooleanMethodV",
+    "CallNonvirtualBooleanMethodA",
+    "CallNonvirtualByteMethod",
+    "CallNonvirtualByteMethodV",
+    "CallNonvirtualByteMethodA",
+    "CallNonvirtualCharMethod",
+    "CallNonvirtualCharMethodV",
+    "CallNonvirtualCharMethodA",
+    "CallNonvirtualShortMethod",
+    "CallNonvirtualShortMethodV",
+    "CallNonvirtualShortMethodA",
+    "CallNonvirtualIntMethod",
+    "CallNonvirtualIntMethodV",
+    "CallNonvirtualIntMethodA",
+    "CallNonvirtualLongMethod",
+    "CallNonvirtualLongMethodV",
+    "CallNonvirtualLongMethodA",
+    "CallNonvirtualFloatMethod",
+    "CallNonvirtualFloatMethodV",
+    "CallNonvirtualFloatMethodA",
+    "CallNonvirtualDoubleMethod",
+    "CallNonvirtualDoubleMethodV",
+    "CallNonvirtualDoubleMethodA",
+    "CallNonvirtualVoidMethod",
+    "CallNonvirtualVoidMethodV",
+    "CallNonvirtualVoidMethodA",
+    "GetFieldID",
+    "GetObjectField",
+    "GetBooleanField",
+    "GetByteField",
+    "GetCharField",
+    "GetShortField",
+    "GetIntField",
+    "GetLongField",
+    "GetFloatField",
+    "GetDoubleField",
+    "SetObjectField",
+    "SetBooleanField",
+    "SetByteField",
+    "SetCharField",
+    "SetShortField",
+    "SetIntField",
+    "SetLongField",
+    "SetFloatField",
+    "SetDoubleField",
+    "GetStaticMethodID",
+    "CallStaticObjectMethod",
+    "CallStaticObjectMethodV",
+    "CallStaticObjectMethodA",
+    "CallStaticBooleanMethod",
+    "CallStaticBooleanMethodV",
+    "CallStaticBooleanMethodA",
+    "CallStaticByteMethod",
+    "CallStaticByteMethodV",
+    "CallStaticByteMethodA",
+    "CallStaticCharMethod",
+    "CallStaticCharMethodV",
+    "CallStaticCharMethodA",
+    "CallStaticShortMethod",
+    "CallStaticShortMethodV",
+    "CallStaticShortMethodA",
+    "CallStaticIntMethod",
+    "CallStaticIntMethodV",
+    "CallStaticIntMethodA",
+    "CallStaticLongMethod",
+    "CallStaticLongMethodV",
+    "CallStaticLongMethodA",
+    "CallStaticFloatMethod",
+    "CallStaticFloatMethodV",
+    "CallStaticFloatMethodA",
+    "CallStaticDoubleMethod",
+    "CallStaticDoubleMethodV",
+    "CallStaticDoubleMethodA",
+    "CallStaticVoidMethod",
+    "CallStaticVoidMethodV",
+    "CallStaticVoidMethodA",
+    "GetStaticFieldID",
+    "GetStaticObjectField",
+    "GetStaticBooleanField",
+    "GetStaticByteField",
+    "GetStaticCharField",
+    "GetStaticShortField",
+    "GetStaticIntField",
+    "GetStaticLongField",
+    "GetStaticFloatField",
+    "GetStaticDoubleField",
+    "SetStaticObjectField",
+    "SetStaticBooleanField",
+    "SetStaticByteField",
+    "SetStaticCharField",
+    "SetStaticShortField",
+    "SetStaticIntField",
+    "SetStaticLongField",
+    "SetStaticFloatField",
+    "SetStaticDoubleField",
+    "NewString",
+    "GetStringLength",
+    "GetStringChars",
+    "ReleaseStringChars",
+    "NewStringUTF",
+    "GetStringUTFLength",
+    "GetStringUTFChars",
+    "ReleaseStringUTFChars",
+    "GetArrayLength",
+    "NewObjectArray",
+    "GetObjectArrayElement",
+    "SetObjectArrayElement",
+    "NewBooleanArray",
+    "NewByteArray",
+    "NewCharArray",
+    "NewShortArray",
+    "NewIntArray",
+    "NewLongArray",
+    "NewFloatArray",
+    "NewDoubleArray",
+    "GetBooleanArrayElements",
+    "GetByteArrayElements",
+    "GetCharArrayElements",
+    "GetShortArrayElements",
+    "GetIntArrayElements",
+    "GetLongArrayElements",
+    "GetFloatArrayElements",
+    "GetDoubleArrayElements",
+    "ReleaseBooleanArrayElements",
+    "ReleaseByteArrayElements",
+    "ReleaseCharArrayElements",
+    "ReleaseShortArrayElements",
+    "ReleaseIntArrayElements",
+    "ReleaseLongArrayElements",
+    "ReleaseFloatArrayElements",
+    "ReleaseDoubleArrayElements",
+    "GetBooleanArrayRegion",
+    "GetByteArrayRegion",
+    "GetCharArrayRegion",
+    "GetShortArrayRegion",
+    "GetIntArrayRegion",
+    "GetLongArrayRegion",
+    "GetFloatArrayRegion",
+    "GetDoubleArrayRegion",
+    "SetBooleanArrayRegion",
+    "SetByteArrayRegion",
+    "SetCharArrayRegion",
+    "SetShortArrayRegion",
+    "SetIntArrayRegion",
+    "SetLongArrayRegion",
+    "SetFloatArrayRegion",
+    "SetDoubleArrayRegion",
+    "RegisterNatives",
+    "UnregisterNatives",
+    "MonitorEnter",
+    "MonitorExit",
+    "GetJavaVM",
+    "GetStringRegion",
+    "GetStringUTFRegion",
+    "GetPrimitiveArrayCritical",
+    "ReleasePrimitiveArrayCritical",
+    "GetStringCritical",
+    "ReleaseStringCritical",
+    "NewWeakGlobalRef",
+    "DeleteWeakGlobalRef",
+    "ExceptionCheck",
+    "NewDirectByteBuffer",
+    "GetDirectBufferAddress",
+    "GetDirectBufferCapacity",
+    "GetObjectRefType",
+    "GetModule",
+    "IsVirtualThread",
+];
+
+/// Names of the functions in the `JavaVM` function table, indexed by their position in the table.
+const VM_NAMES: [&str; 8] = [
+    "reserved",
+    "reserved",
+    "reserved",
+    "DestroyJavaVM",
+    "AttachCurrentThread",
+    "DetachCurrentThread",
+    "GetEnv",
+    "AttachCurrentThreadAsDaemon",
+];
+
+/// Counter and accumulated time of one function.
+#[derive(Debug)]
+struct Slot {
+    /// Amount of calls.
+    count: AtomicU64,
+    /// Accumulated time of all calls in nanoseconds.
+    total_ns: AtomicU64,
+}
+
+impl Slot {
+    /// A slot without calls.
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: Self = Self {
+        count: AtomicU64::new(0),
+        total_ns: AtomicU64::new(0),
+    };
+}
+
+/// Slots of the `JNIEnv` functions.
+static ENV_SLOTS: [Slot; 235] = [Slot::EMPTY; 235];
+
+/// Slots of the `JavaVM` functions.
+static VM_SLOTS: [Slot; 8] = [Slot::EMPTY; 8];
+
+///
+/// A function pointer from a function table that records the call when it is dropped.
+///
+/// Derefs to the function pointer so it can be called like it.
+/// It is a temporary of the statement that makes the call and is therefore dropped right after the call returns.
+///
+pub(crate) struct MeteredFn<X> {
+    /// The function.
+    function: X,
+    /// Slot of the function.
+    slot: &'static Slot,
+    /// Time at which the function was looked up.
+    start: Instant,
+}
+
+impl<X> MeteredFn<X> {
+    /// Wraps a function of the `JNIEnv` function table.
+    pub(crate) fn env(index: usize, function: X) -> Self {
+        Self {
+            function,
+            slot: &ENV_SLOTS[index],
+            start: Instant::now(),
+        }
+    }
+
+    /// Wraps a function of the `JavaVM` function table.
+    pub(crate) fn vm(index: usize, function: X) -> Self {
+        Self {
+            function,
+            slot: &VM_SLOTS[index],
+            start: Instant::now(),
+        }
+    }
+}
+
+impl<X> Deref for MeteredFn<X> {
+    type Target = X;
+
+    fn deref(&self) -> &Self::Target {
+        &self.function
+    }
+}
+
+impl<X> Drop for MeteredFn<X> {
+    fn drop(&mut self) {
+        let elapsed = u64::try_from(self.start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+        self.slot.count.fetch_add(1, Ordering::Relaxed);
+        self.slot.total_ns.fetch_add(elapsed, Ordering::Relaxed);
+    }
+}
+
+///
+/// Returns the name, amount of calls and accumulated time in nanoseconds of every JNI function that was called
+/// since the process started or `reset` was last called.
+///
+/// Functions that were not called are omitted.
+/// The `JNIEnv` functions come first in the order of the `JNIEnv` function table, followed by the `JavaVM` functions.
+///
+/// The counters are read one after another while other threads may still be calling JNI functions,
+/// so the snapshot is not necessarily consistent across functions.
+///
+#[must_use]
+pub fn snapshot() -> Vec<(&'static str, u64, u64)> {
+    let env = ENV_NAMES.iter().zip(ENV_SLOTS.iter());
+    let vm = VM_NAMES.iter().zip(VM_SLOTS.iter());
+    env.chain(vm)
+        .filter_map(|(name, slot)| {
+            let count = slot.count.load(Ordering::Relaxed);
+            if count == 0 {
+                return None;
+            }
+            Some((*name, count, slot.total_ns.load(Ordering::Relaxed)))
+        })
+        .collect()
+}
+
+///
+/// Sets the amount of calls and the accumulated time of every JNI function to 0.
+///
+/// Calls that are in progress while this is called are recorded when they return.
+///
+pub fn reset() {
+    for slot in ENV_SLOTS.iter().chain(VM_SLOTS.iter()) {
+        slot.count.store(0, Ordering::Relaxed);
+        slot.total_ns.store(0, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,290 @@
+//!
+//! Conversion between rust strings and the modified UTF-8 encoding used by the JVM.
+//!
+//! JNI functions such as `NewStringUTF`, `GetStringUTFChars`, `FindClass` or `GetMethodID`
+//! as well as the constant pool of class files use modified UTF-8 instead of standard UTF-8. It differs in two ways:
+//! * The null character is encoded as the two bytes `C0 80`, so the encoded string never contains a 0 byte.
+//! * Characters outside the Basic Multilingual Plane are encoded as their UTF-16 surrogate pair,
+//!   with each surrogate encoded as three bytes, instead of as one four byte sequence.
+//!
+//! All other characters are encoded exactly like in standard UTF-8.
+//!
+//! # Example
+//! ```rust
+//! use jni_simple::mutf8;
+//!
+//! let encoded = mutf8::encode("nul\0 and \u{1F600}");
+//! assert_eq!(encoded.as_ref(), b"nul\xC0\x80 and \xED\xA0\xBD\xED\xB8\x80");
+//! assert_eq!(mutf8::decode(&encoded).unwrap(), "nul\0 and \u{1F600}");
+//! ```
+//!
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+
+///
+/// Error returned by `decode` if the bytes are not valid modified UTF-8.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mutf8Error {
+    /// Index of the first byte of the first invalid sequence.
+    valid_up_to: usize,
+}
+
+impl Mutf8Error {
+    ///
+    /// Returns the index of the first byte that is not part of a valid sequence.
+    /// All bytes before this index are valid modified UTF-8.
+    ///
+    #[must_use]
+    pub const fn valid_up_to(&self) -> usize {
+        self.valid_up_to
+    }
+}
+
+impl Display for Mutf8Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("invalid modified utf-8 sequence at index {}", self.valid_up_to))
+    }
+}
+
+impl std::error::Error for Mutf8Error {}
+
+///
+/// Returns true if the modified UTF-8 encoding of `s` is identical to its standard UTF-8 encoding.
+///
+/// This is the case if `s` contains neither the null character nor characters outside the Basic Multilingual Plane.
+///
+#[must_use]
+pub fn is_plain_utf8_compatible(s: &str) -> bool {
+    //Four byte sequences are the only ones starting with 0xF0 or higher.
+    s.bytes().all(|b| b != 0 && b < 0xF0)
+}
+
+///
+/// Encodes a string as modified UTF-8.
+///
+/// The result is not 0 terminated.
+///
+/// # Returns
+/// The bytes of `s` if they are already valid modified UTF-8, a new allocation otherwise.
+///
+#[must_use]
+pub fn encode(s: &str) -> Cow<'_, [u8]> {
+    if is_plain_utf8_compatible(s) {
+        return Cow::Borrowed(s.as_bytes());
+    }
+
+    let mut encoded = Vec::with_capacity(s.len() + s.len() / 2);
+    for c in s.chars() {
+        match c {
+            '\0' => encoded.extend_from_slice(&[0xC0, 0x80]),
+            c if c.len_utf8() == 4 => {
+                for unit in c.encode_utf16(&mut [0; 2]) {
+                    encode_unit(&mut encoded, *unit);
+                }
+            }
+            c => encoded.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
+    Cow::Owned(encoded)
+}
+
+/// Appends the three byte encoding of a UTF-16 code unit.
+fn encode_unit(encoded: &mut Vec<u8>, unit: u16) {
+    let [high, low] = unit.to_be_bytes();
+    encoded.extend_from_slice(&[0xE0 | (high >> 4), 0x80 | ((high & 0x0F) << 2) | (low >> 6), 0x80 | (low & 0x3F)]);
+}
+
+///
+/// Decodes modified UTF-8.
+///
+/// # Errors
+/// If `bytes` contains a 0 byte, a four byte sequence, an overlong or truncated sequence or an unpaired surrogate.
+///
+pub fn decode(bytes: &[u8]) -> Result<String, Mutf8Error> {
+    decode_with(bytes, false)
+}
+
+///
+/// Decodes modified UTF-8.
+///
+/// Invalid sequences and unpaired surrogates are replaced by `U+FFFD REPLACEMENT CHARACTER`.
+///
+#[must_use]
+pub fn decode_lossy(bytes: &[u8]) -> String {
+    decode_with(bytes, true).unwrap_or_default()
+}
+
+/// Decodes modified UTF-8, replacing invalid sequences if `lossy` is true and failing on them otherwise.
+fn decode_with(bytes: &[u8], lossy: bool) -> Result<String, Mutf8Error> {
+    //Valid standard UTF-8 without 0 bytes and four byte sequences means the same in modified UTF-8.
+    //The standard decoder rejects C0 80 and encoded surrogates, those take the slow path.
+    if bytes.iter().all(|b| *b != 0 && *b < 0xF0) {
+        if let Ok(s) = std::str::from_utf8(bytes) {
+            return Ok(s.to_string());
+        }
+    }
+
+    let mut decoded = String::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let Some((unit, len)) = decode_unit(bytes, index) else {
+            if !lossy {
+                return Err(Mutf8Error { valid_up_to: index });
+            }
+            decoded.push(char::REPLACEMENT_CHARACTER);
+            index += 1;
+            continue;
+        };
+
+        if let Some(c) = char::from_u32(u32::from(unit)) {
+            decoded.push(c);
+            index += len;
+            continue;
+        }
+
+        //unit is a surrogate
+        if (0xD800..0xDC00).contains(&unit) {
+            if let Some((low, low_len)) = decode_unit(bytes, index + len).filter(|(low, _)| (0xDC00..0xE000).contains(low)) {
+                let c = char::decode_utf16([unit, low]).next().and_then(Result::ok).unwrap_or(char::REPLACEMENT_CHARACTER);
+                decoded.push(c);
+                index += len + low_len;
+                continue;
+            }
+        }
+
+        if !lossy {
+            return Err(Mutf8Error { valid_up_to: index });
+        }
+        decoded.push(char::REPLACEMENT_CHARACTER);
+        index += len;
+    }
+
+    Ok(decoded)
+}
+
+/// Decodes the one, two or three byte sequence at `index` into a UTF-16 code unit.
+/// Returns the code unit and the length of the sequence or None if the sequence is invalid.
+fn decode_unit(bytes: &[u8], index: usize) -> Option<(u16, usize)> {
+    let continuation = |offset: usize| bytes.get(index + offset).filter(|b| *b & 0xC0 == 0x80).map(|b| u16::from(b & 0x3F));
+
+    let first = *bytes.get(index)?;
+    match first {
+        0x01..=0x7F => Some((u16::from(first), 1)),
+        0xC0..=0xDF => {
+            let unit = (u16::from(first & 0x1F) << 6) | continuation(1)?;
+            //C0 80 is the null character, every other value below 0x80 is overlong.
+            (unit == 0 || unit >= 0x80).then_some((unit, 2))
+        }
+        0xE0..=0xEF => {
+            let unit = (u16::from(first & 0x0F) << 12) | (continuation(1)? << 6) | continuation(2)?;
+            (unit >= 0x800).then_some((unit, 3))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_encode() {
+    assert!(matches!(encode("plain ascii"), Cow::Borrowed(b"plain ascii")));
+    assert!(matches!(encode("\u{E4}\u{20AC}\u{FFFF}"), Cow::Borrowed(_)));
+    assert_eq!(encode("").as_ref(), b"");
+    assert_eq!(encode("\0").as_ref(), b"\xC0\x80");
+    assert_eq!(encode("a\0b").as_ref(), b"a\xC0\x80b");
+    assert_eq!(encode("\u{10000}").as_ref(), b"\xED\xA0\x80\xED\xB0\x80");
+    assert_eq!(encode("\u{10FFFF}").as_ref(), b"\xED\xAF\xBF\xED\xBF\xBF");
+    assert_eq!(encode("x\u{1F600}y").as_ref(), b"x\xED\xA0\xBD\xED\xB8\x80y");
+    assert!(matches!(encode("\0"), Cow::Owned(_)));
+}
+
+#[cfg(test)]
+#[test]
+fn test_is_plain_utf8_compatible() {
+    assert!(is_plain_utf8_compatible(""));
+    assert!(is_plain_utf8_compatible("java/lang/String"));
+    assert!(is_plain_utf8_compatible("\u{E4}\u{20AC}\u{FFFF}"));
+    assert!(!is_plain_utf8_compatible("\0"));
+    assert!(!is_plain_utf8_compatible("\u{1F600}"));
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode() {
+    assert_eq!(decode(b""), Ok(String::new()));
+    assert_eq!(decode(b"plain"), Ok("plain".to_string()));
+    assert_eq!(decode("\u{E4}\u{20AC}\u{FFFF}".as_bytes()), Ok("\u{E4}\u{20AC}\u{FFFF}".to_string()));
+    assert_eq!(decode(b"a\xC0\x80b"), Ok("a\0b".to_string()));
+    assert_eq!(decode(b"\xED\xA0\x80\xED\xB0\x80"), Ok("\u{10000}".to_string()));
+    assert_eq!(decode(b"x\xED\xA0\xBD\xED\xB8\x80y"), Ok("x\u{1F600}y".to_string()));
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_invalid() {
+    let invalid: [(&[u8], usize); 12] = [
+        (b"a\0b", 1),
+        (b"ab\xF0\x9F\x98\x80", 2),
+        (b"\xC1\xBF", 0),
+        (b"\xE0\x80\x80", 0),
+        (b"\xC0", 0),
+        (b"a\xE2\x82", 1),
+        (b"\x80", 0),
+        (b"\xFF", 0),
+        (b"ab\xED\xA0\xBD", 2),
+        (b"\xED\xB8\x80", 0),
+        (b"\xED\xA0\xBDx", 0),
+        (b"\xED\xA0\xBD\xED\xA0\xBD", 0),
+    ];
+
+    for (bytes, valid_up_to) in invalid {
+        assert_eq!(decode(bytes), Err(Mutf8Error { valid_up_to }), "{bytes:?}");
+    }
+
+    assert_eq!(decode(b"a\0").map_err(|err| err.to_string()), Err("invalid modified utf-8 sequence at index 1".to_string()));
+}
+
+#[cfg(test)]
+#[test]
+fn test_decode_lossy() {
+    assert_eq!(decode_lossy(b"a\xC0\x80b"), "a\0b");
+    assert_eq!(decode_lossy(b"a\0b"), "a\u{FFFD}b");
+    assert_eq!(decode_lossy(b"ab\xED\xA0\xBD"), "ab\u{FFFD}");
+    assert_eq!(decode_lossy(b"\xED\xB8\x80x"), "\u{FFFD}x");
+    assert_eq!(decode_lossy(b"\xED\xA0\xBD\xED\xA0\xBD\xED\xB8\x80"), "\u{FFFD}\u{1F600}");
+    assert_eq!(decode_lossy(b"\xF0\x9F\x98\x80"), "\u{FFFD}\u{FFFD}\u{FFFD}\u{FFFD}");
+    assert_eq!(decode_lossy(b"\xC1\xBF"), "\u{FFFD}\u{FFFD}");
+}
+
+#[cfg(test)]
+#[test]
+fn test_round_trip() {
+    //xorshift, so the test is deterministic without depending on a random number crate.
+    let mut state: u32 = 0x2545_F491;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state
+    };
+
+    for _ in 0..10_000 {
+        let len = next() % 16;
+        let s = (0..len)
+            .map(|_| match next() % 4 {
+                0 => '\0',
+                1 => char::from_u32(next() % 0x80).unwrap_or('a'),
+                2 => char::from_u32(next() % 0x1_0000).unwrap_or('b'),
+                _ => char::from_u32(0x1_0000 + next() % 0x10_0000).unwrap_or('c'),
+            })
+            .collect::<String>();
+
+        let encoded = encode(&s);
+        assert!(!encoded.contains(&0));
+        assert!(encoded.iter().all(|b| *b < 0xF0));
+        assert_eq!(decode(&encoded).as_ref(), Ok(&s));
+        assert_eq!(decode_lossy(&encoded), s);
+    }
+}
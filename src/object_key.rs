@@ -0,0 +1,192 @@
+//!
+//! Hash map key that identifies a java object by identity.
+//!
+
+use crate::{jint, jobject, JNIEnv, JavaVM, JNI_VERSION_1_2};
+use std::ffi::c_void;
+use std::hash::{Hash, Hasher};
+use std::ptr::null_mut;
+use sync_ptr::SyncMutPtr;
+
+///
+/// Identifies a java object by identity, for use as key in a `HashMap` or `HashSet`.
+///
+/// Raw `jobject` values cannot be used as keys, because two references to the same object usually have different values.
+/// An `ObjectKey` holds its own global or weak global reference to the object and its `System.identityHashCode`.
+///
+/// `Hash` only uses the identity hash code, which is fixed when the key is created.
+/// `Eq` first compares the identity hash codes and the `JavaVM` and only calls `IsSameObject` if both match,
+/// so different objects that share an identity hash code are never equal keys.
+/// `IsSameObject` is called with the `JNIEnv` of the current thread, obtained from the `JavaVM` of the key.
+/// If the current thread is not attached, it is attached for the comparison and detached again afterwards,
+/// if attaching fails this panics.
+/// Use `same_object` to compare keys with a `JNIEnv` that is already at hand.
+///
+/// Keys must not be compared while the current thread has a pending exception or holds a critical reference,
+/// as `IsSameObject` must not be called then. If the asserts feature is enabled this panics.
+/// They must also not be compared once their `JavaVM` was destroyed.
+///
+/// An `ObjectKey` does not delete its reference when it is dropped, because it has no `JNIEnv` to do so.
+/// Call `release` when the key is no longer needed or the reference leaks.
+///
+/// A weak key does not keep its object alive. Once its object is garbage collected it only equals other weak keys
+/// with the same identity hash code whose object was also collected, so weak keys should be removed
+/// from a map once their object is collected.
+///
+/// # Example
+/// ```rust
+/// use jni_simple::*;
+/// use std::collections::HashMap;
+///
+/// unsafe fn count_instances(env: JNIEnv, counts: &mut HashMap<ObjectKey, usize>, obj: jobject) {
+///     let class = env.GetObjectClass(obj);
+///     let Some(key) = env.object_key(class) else {
+///         return; //Exception is pending
+///     };
+///     env.DeleteLocalRef(class);
+///     if let Some(count) = counts.get_mut(&key) {
+///         *count += 1;
+///         key.release(&env);
+///         return;
+///     }
+///     counts.insert(key, 1);
+/// }
+/// ```
+///
+#[derive(Debug)]
+pub struct ObjectKey {
+    /// The vm of the reference, keys of different vms are never equal.
+    vm: JavaVM,
+    /// Global or weak global reference to the object.
+    reference: SyncMutPtr<c_void>,
+    /// True if `reference` is a weak global reference.
+    weak: bool,
+    /// `System.identityHashCode` of the object.
+    hash: jint,
+}
+
+impl ObjectKey {
+    /// Creates a key from a reference that is owned by the key.
+    pub(crate) const unsafe fn new(vm: JavaVM, reference: jobject, weak: bool, hash: jint) -> Self {
+        Self {
+            vm,
+            reference: SyncMutPtr::new(reference),
+            weak,
+            hash,
+        }
+    }
+
+    ///
+    /// Returns the global or weak global reference held by this key.
+    ///
+    /// The reference is owned by the key and must not be deleted by the caller.
+    ///
+    #[must_use]
+    pub const fn reference(&self) -> jobject {
+        self.reference.inner()
+    }
+
+    ///
+    /// Returns true if the key holds a weak global reference.
+    ///
+    #[must_use]
+    pub const fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    ///
+    /// Returns the identity hash code of the object.
+    ///
+    #[must_use]
+    pub const fn identity_hash(&self) -> jint {
+        self.hash
+    }
+
+    ///
+    /// Checks if this key and `other` identify the same object.
+    ///
+    /// Behaves like `==`, but uses `env` instead of obtaining the `JNIEnv` of the current thread from the `JavaVM` of the key.
+    /// Two weak keys whose objects were both garbage collected are reported as the same object.
+    ///
+    /// # Panics
+    /// if asserts feature is enabled and UB was detected
+    ///
+    /// # Safety
+    ///
+    /// Current thread must not be detached from JNI.
+    ///
+    /// Current thread must not be currently throwing an exception.
+    ///
+    /// Current thread does not hold a critical reference.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/functions.html#GetPrimitiveArrayCritical_ReleasePrimitiveArrayCritical>
+    ///
+    /// Neither key may have been released.
+    ///
+    /// `env` must belong to the same JVM as the keys.
+    ///
+    #[must_use]
+    pub unsafe fn same_object(&self, env: &JNIEnv, other: &Self) -> bool {
+        if self.hash != other.hash || !self.vm.ptr_eq(&other.vm) {
+            return false;
+        }
+
+        self.reference() == other.reference() || env.IsSameObject(self.reference(), other.reference())
+    }
+
+    ///
+    /// Deletes the reference held by this key.
+    ///
+    /// # Panics
+    /// if asserts feature is enabled and UB was detected
+    ///
+    /// # Safety
+    ///
+    /// Current thread must not be detached from JNI.
+    ///
+    /// Current thread does not hold a critical reference.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/functions.html#GetPrimitiveArrayCritical_ReleasePrimitiveArrayCritical>
+    ///
+    /// `env` must belong to the same JVM as the key.
+    ///
+    pub unsafe fn release(self, env: &JNIEnv) {
+        if self.weak {
+            env.DeleteWeakGlobalRef(self.reference());
+        } else {
+            env.DeleteGlobalRef(self.reference());
+        }
+    }
+}
+
+impl Hash for ObjectKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl PartialEq for ObjectKey {
+    fn eq(&self, other: &Self) -> bool {
+        if self.hash != other.hash || !self.vm.ptr_eq(&other.vm) {
+            return false;
+        }
+
+        if self.reference() == other.reference() {
+            return true;
+        }
+
+        unsafe {
+            if let Ok(env) = self.vm.GetEnv(JNI_VERSION_1_2) {
+                return env.IsSameObject(self.reference(), other.reference());
+            }
+
+            let env = self
+                .vm
+                .AttachCurrentThread_str(JNI_VERSION_1_2, None, null_mut())
+                .expect("failed to attach thread to compare ObjectKey");
+            let result = env.IsSameObject(self.reference(), other.reference());
+            _ = self.vm.DetachCurrentThread();
+            result
+        }
+    }
+}
+
+impl Eq for ObjectKey {}
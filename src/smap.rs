@@ -0,0 +1,527 @@
+//!
+//! Parser for the source map (SMAP) format of JSR-45.
+//!
+//! Compilers of languages other than java, such as Kotlin or JSP, store an SMAP in the `SourceDebugExtension`
+//! attribute of the class files they produce. It maps the line numbers of the class file, which are the ones
+//! that appear in stack traces, back to the lines of the original sources.
+//!
+//! An SMAP contains one or more strata. Each stratum lists the source files of one language level in its file section
+//! and the mapping of output lines to lines of those files in its line section.
+//!
+//! # Example
+//! ```rust
+//! use jni_simple::smap;
+//!
+//! let smap = smap::parse("SMAP\nindex_jsp.java\nJSP\n*S JSP\n*F\n+ 0 index.jsp\nWEB-INF/index.jsp\n*L\n1,5:62\n6:67,2\n*E\n").unwrap();
+//! assert_eq!(smap.default_stratum(), "JSP");
+//! assert_eq!(smap.map_line(64, None), Some((0, 3)));
+//! assert_eq!(smap.map_line(68, Some("JSP")), Some((0, 6)));
+//! assert_eq!(smap.stratum("JSP").and_then(|stratum| stratum.file(0)).map(|file| file.name.as_str()), Some("index.jsp"));
+//! ```
+//!
+
+use std::fmt::{Display, Formatter};
+
+///
+/// Error returned by `parse` if a string is not a valid SMAP.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmapError {
+    /// 1 based number of the line that could not be parsed.
+    line: usize,
+    /// What is wrong with the line.
+    reason: &'static str,
+}
+
+impl SmapError {
+    ///
+    /// Returns the 1 based number of the line of the SMAP that could not be parsed.
+    ///
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    ///
+    /// Returns a description of what is wrong with the line.
+    ///
+    #[must_use]
+    pub const fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl Display for SmapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("invalid smap at line {}: {}", self.line, self.reason))
+    }
+}
+
+impl std::error::Error for SmapError {}
+
+///
+/// A source file of a stratum, from a line of its file section.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileInfo {
+    /// The id that line sections use to refer to this file.
+    pub id: u32,
+    /// The name of the file, usually without directory.
+    pub name: String,
+    /// The path of the file relative to the source root, if the file section contains it.
+    pub path: Option<String>,
+}
+
+///
+/// A line of the line section of a stratum.
+///
+/// `repeat_count` consecutive input lines, starting at `input_start_line`, each map to `output_line_increment`
+/// consecutive output lines, starting at `output_start_line`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineInfo {
+    /// The first input line.
+    pub input_start_line: u32,
+    /// The id of the file the input lines belong to.
+    pub file_id: u32,
+    /// The amount of input lines.
+    pub repeat_count: u32,
+    /// The output line of the first input line.
+    pub output_start_line: u32,
+    /// The amount of output lines of each input line.
+    pub output_line_increment: u32,
+}
+
+impl LineInfo {
+    /// Returns the input line that is mapped to `output_line` by this line info.
+    const fn map_line(&self, output_line: u32) -> Option<u32> {
+        if self.output_line_increment == 0 || output_line < self.output_start_line {
+            return None;
+        }
+
+        let offset = (output_line - self.output_start_line) / self.output_line_increment;
+        if offset >= self.repeat_count {
+            return None;
+        }
+
+        self.input_start_line.checked_add(offset)
+    }
+}
+
+///
+/// A stratum of an SMAP.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stratum {
+    /// The id of the stratum, i.e. `Kotlin` or `JSP`.
+    id: String,
+    /// The file section.
+    files: Vec<FileInfo>,
+    /// The line section.
+    lines: Vec<LineInfo>,
+}
+
+impl Stratum {
+    ///
+    /// Returns the id of the stratum, i.e. `Kotlin` or `JSP`.
+    ///
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    ///
+    /// Returns the files of the file section in the order in which they appear in the SMAP.
+    ///
+    #[must_use]
+    pub fn files(&self) -> &[FileInfo] {
+        &self.files
+    }
+
+    ///
+    /// Returns the file with the given id.
+    ///
+    #[must_use]
+    pub fn file(&self, id: u32) -> Option<&FileInfo> {
+        self.files.iter().find(|file| file.id == id)
+    }
+
+    ///
+    /// Returns the entries of the line section in the order in which they appear in the SMAP.
+    ///
+    #[must_use]
+    pub fn lines(&self) -> &[LineInfo] {
+        &self.lines
+    }
+
+    ///
+    /// Maps a line number of the class file to the file id and line of the input source.
+    ///
+    /// If more than one entry of the line section contains the output line, the first one is used.
+    ///
+    #[must_use]
+    pub fn map_line(&self, output_line: u32) -> Option<(u32, u32)> {
+        self.lines.iter().find_map(|line| line.map_line(output_line).map(|input_line| (line.file_id, input_line)))
+    }
+}
+
+///
+/// A parsed SMAP.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Smap {
+    /// Name of the file the SMAP describes, usually the name of the generated source file.
+    output_file: String,
+    /// Id of the stratum that should be used if none is requested.
+    default_stratum: String,
+    /// The strata in the order in which they appear in the SMAP.
+    strata: Vec<Stratum>,
+}
+
+impl Smap {
+    ///
+    /// Returns the name of the generated file the SMAP describes.
+    ///
+    #[must_use]
+    pub fn output_file(&self) -> &str {
+        &self.output_file
+    }
+
+    ///
+    /// Returns the id of the stratum that should be used if none is requested.
+    ///
+    /// This may be `Java`, which means that the line numbers of the class file should be used as is.
+    ///
+    #[must_use]
+    pub fn default_stratum(&self) -> &str {
+        &self.default_stratum
+    }
+
+    ///
+    /// Returns the strata in the order in which they appear in the SMAP.
+    ///
+    #[must_use]
+    pub fn strata(&self) -> &[Stratum] {
+        &self.strata
+    }
+
+    ///
+    /// Returns the stratum with the given id.
+    ///
+    #[must_use]
+    pub fn stratum(&self, id: &str) -> Option<&Stratum> {
+        self.strata.iter().find(|stratum| stratum.id == id)
+    }
+
+    ///
+    /// Maps a line number of the class file to the file id and line of the input source of a stratum.
+    ///
+    /// # Arguments
+    /// * `output_line` - the line number of the class file, i.e. from a stack trace.
+    /// * `stratum` - the id of the stratum to use, None for the default stratum.
+    ///
+    /// # Returns
+    /// The id of the file in the file section of the stratum and the line in that file.
+    /// None if the stratum does not exist or does not map the line.
+    ///
+    #[must_use]
+    pub fn map_line(&self, output_line: u32, stratum: Option<&str>) -> Option<(u32, u32)> {
+        self.stratum(stratum.unwrap_or(&self.default_stratum))?.map_line(output_line)
+    }
+}
+
+/// Section of an SMAP that is currently being parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Section {
+    /// Between the header and the first section.
+    None,
+    /// A file section.
+    File,
+    /// A line section.
+    Line,
+    /// A vendor section, an unknown section or after the end section. Lines are ignored.
+    Ignored,
+}
+
+///
+/// Parses an SMAP, as stored in the `SourceDebugExtension` attribute of a class file.
+///
+/// * Lines may be terminated by `\n` or `\r\n`.
+/// * Vendor sections (`*V`) and unknown sections are ignored.
+/// * The `LineFileID` of a line section entry defaults to the one of the previous entry of the stratum, or 0.
+/// * Kotlin terminates every stratum with `*E`, so a `*S` after an end section starts a new stratum.
+///   Any other content after an end section is ignored.
+/// * Embedded SMAPs (`*O` and `*C`), which only occur in SMAPs that were not yet resolved by a post processor, are rejected.
+///
+/// # Errors
+/// If the header is missing or a file or line section contains a malformed line.
+///
+pub fn parse(smap: &str) -> Result<Smap, SmapError> {
+    let mut lines = smap
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .enumerate()
+        .map(|(index, line)| (index + 1, line));
+    let mut header = || lines.next().map(|(_, line)| line);
+    if header() != Some("SMAP") {
+        return Err(SmapError {
+            line: 1,
+            reason: "missing SMAP header",
+        });
+    }
+    let output_file = header()
+        .ok_or(SmapError {
+            line: 2,
+            reason: "missing output file name",
+        })?
+        .to_string();
+    let default_stratum = header()
+        .ok_or(SmapError {
+            line: 3,
+            reason: "missing default stratum",
+        })?
+        .to_string();
+
+    let mut strata: Vec<Stratum> = Vec::new();
+    let mut section = Section::None;
+    let mut file_id = 0;
+    let mut ended = false;
+    while let Some((number, line)) = lines.next() {
+        let error = |reason| SmapError { line: number, reason };
+        if let Some(id) = line.strip_prefix("*S ") {
+            strata.push(Stratum {
+                id: id.trim().to_string(),
+                files: Vec::new(),
+                lines: Vec::new(),
+            });
+            section = Section::Ignored;
+            file_id = 0;
+            ended = false;
+            continue;
+        }
+
+        if ended {
+            continue;
+        }
+
+        if line.starts_with('*') {
+            section = match line.split_whitespace().next().unwrap_or(line) {
+                "*F" => Section::File,
+                "*L" => Section::Line,
+                "*E" => {
+                    ended = true;
+                    Section::Ignored
+                }
+                "*O" | "*C" => return Err(error("embedded smaps are not supported")),
+                _ => Section::Ignored,
+            };
+            if matches!(section, Section::File | Section::Line) && strata.is_empty() {
+                return Err(error("section outside of a stratum"));
+            }
+            continue;
+        }
+
+        let Some(stratum) = strata.last_mut() else {
+            if section == Section::None && line.trim().is_empty() {
+                continue;
+            }
+            return Err(error("content outside of a stratum"));
+        };
+
+        match section {
+            Section::File => {
+                let (with_path, entry) = line.strip_prefix('+').map_or((false, line), |entry| (true, entry));
+                let (id, name) = entry.trim_start().split_once(' ').ok_or_else(|| error("file entry without name"))?;
+                let id = id.parse().map_err(|_| error("invalid file id"))?;
+                let path = if with_path {
+                    let (_, path) = lines.next().ok_or_else(|| error("missing path of file entry"))?;
+                    Some(path.to_string())
+                } else {
+                    None
+                };
+                stratum.files.push(FileInfo { id, name: name.to_string(), path });
+            }
+            Section::Line => {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let info = parse_line_info(line.trim(), file_id).ok_or_else(|| error("malformed line entry"))?;
+                file_id = info.file_id;
+                stratum.lines.push(info);
+            }
+            Section::None | Section::Ignored => {}
+        }
+    }
+
+    Ok(Smap {
+        output_file,
+        default_stratum,
+        strata,
+    })
+}
+
+/// Parses `InputStartLine[#LineFileID][,RepeatCount]:OutputStartLine[,OutputLineIncrement]`.
+fn parse_line_info(line: &str, previous_file_id: u32) -> Option<LineInfo> {
+    let (input, output) = line.split_once(':')?;
+    let (input, repeat_count) = input.split_once(',').map_or((input, Ok(1)), |(input, repeat)| (input, repeat.parse()));
+    let (input_start_line, file_id) = input.split_once('#').map_or((input, Ok(previous_file_id)), |(input, id)| (input, id.parse()));
+    let (output_start_line, output_line_increment) = output.split_once(',').map_or((output, Ok(1)), |(output, increment)| (output, increment.parse()));
+
+    Some(LineInfo {
+        input_start_line: input_start_line.parse().ok()?,
+        file_id: file_id.ok()?,
+        repeat_count: repeat_count.ok()?,
+        output_start_line: output_start_line.parse().ok()?,
+        output_line_increment: output_line_increment.ok()?,
+    })
+}
+
+/// SMAP of a Kotlin class that calls an inline function of the standard library.
+#[cfg(test)]
+const KOTLIN_SMAP: &str = "SMAP
+Main.kt
+Kotlin
+*S Kotlin
+*F
++ 1 Main.kt
+MainKt
++ 2 _Collections.kt
+kotlin/collections/CollectionsKt___CollectionsKt
+*L
+1#1,12:1
+1863#2,2:13
+*E
+*S KotlinDebug
+*F
++ 1 Main.kt
+MainKt
+*L
+5#1:13,2
+*E
+";
+
+/// SMAP of a JSP page compiled by Jasper, with an included page.
+#[cfg(test)]
+const JSP_SMAP: &str = "SMAP\r
+index_jsp.java\r
+JSP\r
+*S JSP\r
+*F\r
++ 0 index.jsp\r
+index.jsp\r
+1 header.jspf\r
+*L\r
+1,5:62\r
+6:67,2\r
+1#1,3:69\r
+7,3:72,0\r
+10,2:72\r
+*V\r
+jasper vendor data\r
+*E\r
+";
+
+#[cfg(test)]
+#[test]
+fn test_parse_kotlin() {
+    let smap = parse(KOTLIN_SMAP).expect("valid smap");
+    assert_eq!(smap.output_file(), "Main.kt");
+    assert_eq!(smap.default_stratum(), "Kotlin");
+    assert_eq!(smap.strata().iter().map(Stratum::id).collect::<Vec<_>>(), vec!["Kotlin", "KotlinDebug"]);
+
+    let kotlin = smap.stratum("Kotlin").expect("Kotlin stratum");
+    assert_eq!(
+        kotlin.files(),
+        &[
+            FileInfo {
+                id: 1,
+                name: "Main.kt".to_string(),
+                path: Some("MainKt".to_string()),
+            },
+            FileInfo {
+                id: 2,
+                name: "_Collections.kt".to_string(),
+                path: Some("kotlin/collections/CollectionsKt___CollectionsKt".to_string()),
+            },
+        ]
+    );
+    assert_eq!(
+        kotlin.lines()[1],
+        LineInfo {
+            input_start_line: 1863,
+            file_id: 2,
+            repeat_count: 2,
+            output_start_line: 13,
+            output_line_increment: 1,
+        }
+    );
+
+    assert_eq!(smap.map_line(0, None), None);
+    assert_eq!(smap.map_line(1, None), Some((1, 1)));
+    assert_eq!(smap.map_line(12, None), Some((1, 12)));
+    assert_eq!(smap.map_line(13, None), Some((2, 1863)));
+    assert_eq!(smap.map_line(14, Some("Kotlin")), Some((2, 1864)));
+    assert_eq!(smap.map_line(15, None), None);
+    assert_eq!(smap.map_line(13, Some("KotlinDebug")), Some((1, 5)));
+    assert_eq!(smap.map_line(14, Some("KotlinDebug")), Some((1, 5)));
+    assert_eq!(smap.map_line(1, Some("KotlinDebug")), None);
+    assert_eq!(smap.map_line(1, Some("Java")), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_jsp() {
+    let smap = parse(JSP_SMAP).expect("valid smap");
+    assert_eq!(smap.output_file(), "index_jsp.java");
+    assert_eq!(smap.strata().len(), 1);
+    let jsp = smap.stratum("JSP").expect("JSP stratum");
+    assert_eq!(jsp.file(1).map(|file| (file.name.as_str(), file.path.as_deref())), Some(("header.jspf", None)));
+    assert_eq!(jsp.file(0).and_then(|file| file.path.as_deref()), Some("index.jsp"));
+    assert!(jsp.file(2).is_none());
+
+    //1,5:62
+    assert_eq!(smap.map_line(61, None), None);
+    assert_eq!(smap.map_line(62, None), Some((0, 1)));
+    assert_eq!(smap.map_line(66, None), Some((0, 5)));
+    //6:67,2
+    assert_eq!(smap.map_line(67, None), Some((0, 6)));
+    assert_eq!(smap.map_line(68, None), Some((0, 6)));
+    //1#1,3:69 switches the file
+    assert_eq!(smap.map_line(71, None), Some((1, 3)));
+    //7,3:72,0 maps no output lines, 10,2:72 inherits file 1 from the previous entry
+    assert_eq!(jsp.lines()[3].file_id, 1);
+    assert_eq!(smap.map_line(72, None), Some((1, 10)));
+    assert_eq!(smap.map_line(73, None), Some((1, 11)));
+    assert_eq!(smap.map_line(74, None), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_invalid() {
+    let invalid = [
+        ("", 1, "missing SMAP header"),
+        ("SMAP\n", 2, "missing output file name"),
+        ("SMAP\nA.java\n", 3, "missing default stratum"),
+        ("SMAP\nA.java\nX\n*L\n1:1\n", 4, "section outside of a stratum"),
+        ("SMAP\nA.java\nX\nstray\n", 4, "content outside of a stratum"),
+        ("SMAP\nA.java\nX\n*S X\n*F\n+ 0 A.x\n", 6, "missing path of file entry"),
+        ("SMAP\nA.java\nX\n*S X\n*F\nA.x\n", 6, "file entry without name"),
+        ("SMAP\nA.java\nX\n*S X\n*F\na A.x\n", 6, "invalid file id"),
+        ("SMAP\nA.java\nX\n*S X\n*L\n1:1\n1,a:2\n", 7, "malformed line entry"),
+        ("SMAP\nA.java\nX\n*S X\n*L\n1\n", 6, "malformed line entry"),
+        ("SMAP\nA.java\nX\n*S X\n*L\n1#:1\n", 6, "malformed line entry"),
+        ("SMAP\nA.java\nX\n*O X\n", 4, "embedded smaps are not supported"),
+    ];
+    for (smap, line, reason) in invalid {
+        assert_eq!(parse(smap), Err(SmapError { line, reason }), "{smap:?}");
+    }
+    assert_eq!(parse("").expect_err("invalid").to_string(), "invalid smap at line 1: missing SMAP header");
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_ignores_trailing_content() {
+    let smap = parse("SMAP\nA.java\nX\n*S X\n*L\n1:1\n*E\nanything\n*L\n5:5\n").expect("valid smap");
+    assert_eq!(smap.strata()[0].lines().len(), 1);
+    assert_eq!(smap.map_line(5, None), None);
+}
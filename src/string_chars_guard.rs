@@ -0,0 +1,74 @@
+//!
+//! Guard that releases the UTF-16 chars of a java string.
+//!
+
+use crate::{jchar, jstring, JNIEnv};
+
+///
+/// The UTF-16 chars of a java string obtained with `GetStringCritical` or `GetStringChars`.
+///
+/// Created by `JNIEnv::string_chars_guard`. The chars are released with the matching release function when this is dropped.
+///
+/// While a guard that `is_critical` is alive the current thread is in a critical region,
+/// so no other JNI functions may be called until it is dropped.
+///
+#[derive(Debug)]
+pub struct StringCharsGuard {
+    /// The env that obtained the chars.
+    env: JNIEnv,
+    /// The string the chars belong to.
+    string: jstring,
+    /// Pointer to the chars.
+    chars: *const jchar,
+    /// Amount of chars.
+    len: usize,
+    /// True if the chars were obtained with `GetStringCritical`.
+    critical: bool,
+}
+
+impl StringCharsGuard {
+    /// Creates a guard for chars that were obtained by `env`.
+    pub(crate) const fn new(env: JNIEnv, string: jstring, chars: *const jchar, len: usize, critical: bool) -> Self {
+        Self {
+            env,
+            string,
+            chars,
+            len,
+            critical,
+        }
+    }
+
+    ///
+    /// Returns the UTF-16 chars of the string. The slice is not 0 terminated.
+    ///
+    #[must_use]
+    pub const fn as_slice(&self) -> &[jchar] {
+        if self.len == 0 {
+            return &[];
+        }
+
+        //SAFETY: the jvm guarantees that chars points to len chars until they are released.
+        unsafe { std::slice::from_raw_parts(self.chars, self.len) }
+    }
+
+    ///
+    /// Returns true if the chars were obtained with `GetStringCritical` and will be released with `ReleaseStringCritical`.
+    /// false if they were obtained with `GetStringChars` and will be released with `ReleaseStringChars`.
+    ///
+    #[must_use]
+    pub const fn is_critical(&self) -> bool {
+        self.critical
+    }
+}
+
+impl Drop for StringCharsGuard {
+    fn drop(&mut self) {
+        unsafe {
+            if self.critical {
+                self.env.ReleaseStringCritical(self.string, self.chars);
+            } else {
+                self.env.ReleaseStringChars(self.string, self.chars);
+            }
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//!
+//! Helpers for writing integration tests of code that uses this crate.
+//!
+//! Hotspot JVMs can only be created once per process. This module creates the JVM lazily on first use,
+//! caches it for the rest of the process and attaches every calling thread to it.
+//! Cargo runs the tests of an integration test file in a single process on multiple threads,
+//! so every test in a file will observe the same JVM.
+//!
+//! This module is only available with the `test-util` feature.
+//!
+//! On unix the JVM created by `test_vm` routes its own output through a `vfprintf` hook into `JniCheckCollector`,
+//! so the warnings that `-Xcheck:jni` prints are turned into test failures by `with_test_frame`.
+//!
+//! # Example
+//! ```no_run
+//! use jni_simple::*;
+//! use jni_simple::testing::with_test_frame;
+//!
+//! // Annotate this function with #[test] in your integration test file.
+//! fn test_nano_time() {
+//!     unsafe {
+//!         with_test_frame(|env| {
+//!             let sys = env.FindClass("java/lang/System");
+//!             let nano_time = env.GetStaticMethodID(sys, "nanoTime", "()J");
+//!             assert_ne!(0, env.CallStaticLongMethod0(sys, nano_time));
+//!         });
+//!     }
+//! }
+//! ```
+//!
+
+use crate::{
+    is_jvm_loaded, jint, load_jvm_from_java_home, JNIEnv, JNI_CreateJavaVM, JNI_GetCreatedJavaVMs, JavaVM, JavaVMInitArgs, JavaVMOption, JNI_EDETACHED, JNI_OK, JNI_VERSION_1_8,
+};
+use once_cell::sync::OnceCell;
+use std::ffi::{c_char, c_void, CString};
+use std::ptr::addr_of_mut;
+use std::ptr::null_mut;
+use std::sync::{Mutex, PoisonError};
+
+/// The JVM used by all tests of the current process.
+static TEST_VM: OnceCell<JavaVM> = OnceCell::new();
+
+/// Amount of local references that `with_test_frame` ensures capacity for.
+const TEST_FRAME_CAPACITY: jint = 64;
+
+/// The collector that the `vfprintf` hook of the test JVM writes into.
+static COLLECTOR: JniCheckCollector = JniCheckCollector {
+    state: Mutex::new(CollectorState {
+        installed: false,
+        partial: String::new(),
+        warnings: Vec::new(),
+    }),
+};
+
+/// Prefixes of the lines `-Xcheck:jni` prints when it detects a problem.
+const JNI_CHECK_PREFIXES: [&str; 2] = ["WARNING in native method:", "FATAL ERROR in native method:"];
+
+///
+/// Collects the warnings that `-Xcheck:jni` prints while the JVM created by `test_vm` runs.
+///
+/// `-Xcheck:jni` only prints its findings to stderr, so tests do not fail on them.
+/// `test_vm` installs a `vfprintf` hook when it creates the JVM, which forwards all output of the JVM
+/// to the original stream and collects the lines containing "WARNING in native method:"
+/// or "FATAL ERROR in native method:". A fatal error still terminates the JVM.
+///
+/// The collector is process global. Tests of a file run in parallel, so a warning is not necessarily caused by the test that observes it.
+/// The hook is only installed on unix and if the JVM was created by `test_vm`, in all other cases no warnings are collected.
+///
+/// `with_test_frame` asserts that no warnings were collected. Tests that need to allow some warnings use `test_vm` instead.
+///
+/// # Example
+/// ```no_run
+/// use jni_simple::*;
+/// use jni_simple::testing::{test_vm, JniCheckCollector};
+///
+/// // Annotate this function with #[test] in your integration test file.
+/// fn test_allowed_check_jni_warnings() {
+///     unsafe {
+///         let (_, env) = test_vm();
+///         let sys = env.FindClass("java/lang/System");
+///         let gc = env.GetStaticMethodID(sys, "gc", "()V");
+///         env.CallStaticVoidMethod0(sys, gc);
+///         env.DeleteLocalRef(sys);
+///     }
+///
+///     JniCheckCollector::global().assert_no_warnings(&["JNI call made without checking exceptions"]);
+/// }
+/// ```
+///
+#[derive(Debug)]
+pub struct JniCheckCollector {
+    /// The collected output.
+    state: Mutex<CollectorState>,
+}
+
+/// State of the `JniCheckCollector`.
+#[derive(Debug)]
+struct CollectorState {
+    /// True if the `vfprintf` hook was passed to the JVM.
+    installed: bool,
+    /// Output that was not terminated by a new line yet.
+    partial: String,
+    /// The collected warning lines.
+    warnings: Vec<String>,
+}
+
+impl JniCheckCollector {
+    ///
+    /// Returns the collector of the current process.
+    ///
+    #[must_use]
+    pub fn global() -> &'static Self {
+        &COLLECTOR
+    }
+
+    ///
+    /// Returns true if the `vfprintf` hook was passed to the JVM, so warnings are collected.
+    ///
+    #[must_use]
+    pub fn is_installed(&self) -> bool {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).installed
+    }
+
+    ///
+    /// Returns the warning lines collected so far.
+    ///
+    #[must_use]
+    pub fn warnings(&self) -> Vec<String> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).warnings.clone()
+    }
+
+    ///
+    /// Discards the warning lines collected so far.
+    ///
+    pub fn clear(&self) {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner).warnings.clear();
+    }
+
+    ///
+    /// Panics if a warning was collected that does not contain any of the strings in `allowlist`.
+    ///
+    /// # Arguments
+    /// * `allowlist` - parts of known benign warnings, i.e. `"JNI call made without checking exceptions when required to from CallStaticVoidMethod"`
+    ///
+    /// # Panics
+    /// If a warning was collected that does not contain any of the strings in `allowlist`. The message lists all such warnings.
+    ///
+    pub fn assert_no_warnings(&self, allowlist: &[&str]) {
+        let warnings = self
+            .warnings()
+            .into_iter()
+            .filter(|warning| !allowlist.iter().any(|allowed| warning.contains(allowed)))
+            .collect::<Vec<_>>();
+        assert!(warnings.is_empty(), "-Xcheck:jni reported {} warning(s):\n{}", warnings.len(), warnings.join("\n"));
+    }
+
+    /// Appends output of the JVM and collects all lines it completes that are warnings.
+    fn collect(&self, text: &str) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.partial.push_str(text);
+        while let Some(end) = state.partial.find('\n') {
+            let line = state.partial.drain(..=end).collect::<String>();
+            //The rest of the line may have been printed by java code, i.e. ExceptionDescribe only prints "Exception in thread" through the JVM.
+            if let Some(start) = JNI_CHECK_PREFIXES.iter().find_map(|prefix| line.find(prefix)) {
+                state.warnings.push(line[start..].trim_end().to_string());
+            }
+        }
+        drop(state);
+    }
+}
+
+#[cfg(unix)]
+extern "C" {
+    /// `vsnprintf` from the C library. `args` is a `va_list`, which is passed as a pointer on all supported unix platforms.
+    fn vsnprintf(buffer: *mut c_char, size: usize, format: *const c_char, args: *mut c_void) -> i32;
+
+    /// `fwrite` from the C library.
+    fn fwrite(ptr: *const c_void, size: usize, count: usize, stream: *mut c_void) -> usize;
+}
+
+/// The `vfprintf` hook of the test JVM. Formats the output, writes it to `stream` and passes it to the collector.
+#[cfg(unix)]
+unsafe extern "system" fn vfprintf_hook(stream: *mut c_void, format: *const c_char, args: *mut c_void) -> jint {
+    let mut buffer = [0u8; 4096];
+    let written = vsnprintf(buffer.as_mut_ptr().cast(), buffer.len(), format, args);
+    let Ok(len) = usize::try_from(written) else {
+        return written;
+    };
+
+    //Longer output is truncated, the lines of -Xcheck:jni are far shorter.
+    let text = &buffer[..len.min(buffer.len() - 1)];
+    fwrite(text.as_ptr().cast(), 1, text.len(), stream);
+    COLLECTOR.collect(&String::from_utf8_lossy(text));
+    written
+}
+
+///
+/// Returns the arguments that `test_vm` passes to the JVM if it has to create it.
+///
+/// This turns on the JVMs own JNI checking (`-Xcheck:jni`), which also catches misuse that the `asserts` feature does not detect.
+/// With the `asserts` feature `-Xcheck:jni` does not report missing exception checks,
+/// because the assertions call `ExceptionCheck` before nearly every JNI call.
+///
+#[must_use]
+pub fn default_test_vm_args() -> Vec<String> {
+    vec!["-Xcheck:jni".to_string()]
+}
+
+///
+/// Returns the JVM of the current process and the `JNIEnv` of the current thread.
+///
+/// If no JVM exists yet then it is loaded from `JAVA_HOME` and created with `default_test_vm_args`.
+/// If a JVM was already created by other means, then that JVM is used instead.
+/// The current thread is attached to the JVM if it is not attached yet.
+///
+/// # Panics
+/// If the JVM cannot be loaded, created or attached to.
+///
+/// # Safety
+/// The JVM shared object from `JAVA_HOME` is loaded, the safety of this depends on that shared object.
+/// If a JVM already exists in the current process, it must not have been destroyed.
+///
+pub unsafe fn test_vm() -> (JavaVM, JNIEnv) {
+    let vm = *TEST_VM.get_or_init(|| {
+        if !is_jvm_loaded() {
+            load_jvm_from_java_home().expect("failed to load jvm");
+        }
+
+        let created = JNI_GetCreatedJavaVMs().expect("failed to get created jvms");
+        if let Some(vm) = created.first() {
+            return *vm;
+        }
+
+        create_test_vm()
+    });
+
+    let env = vm.GetEnv(JNI_VERSION_1_8).unwrap_or_else(|code| {
+        assert_eq!(code, JNI_EDETACHED, "GetEnv failed with error code {code}");
+        vm.AttachCurrentThread_str(JNI_VERSION_1_8, None, null_mut()).expect("failed to attach thread")
+    });
+
+    (vm, env)
+}
+
+/// Creates the JVM with `default_test_vm_args` and the `vfprintf` hook of the `JniCheckCollector`.
+unsafe fn create_test_vm() -> JavaVM {
+    let strings = default_test_vm_args()
+        .into_iter()
+        .map(|arg| CString::new(arg).expect("argument contains 0 byte"))
+        .collect::<Vec<_>>();
+    let mut options = strings.iter().map(|arg| JavaVMOption::new(arg.as_ptr().cast_mut(), null_mut())).collect::<Vec<_>>();
+    #[cfg(unix)]
+    options.push(JavaVMOption::new(c"vfprintf".as_ptr().cast_mut(), vfprintf_hook as *mut c_void));
+
+    let mut args = JavaVMInitArgs::from_options(JNI_VERSION_1_8, options.as_mut_slice(), true);
+    let (vm, _) = JNI_CreateJavaVM(addr_of_mut!(args)).expect("failed to create jvm");
+    COLLECTOR.state.lock().unwrap_or_else(PoisonError::into_inner).installed = cfg!(unix);
+    vm
+}
+
+///
+/// Calls the closure with the `JNIEnv` of the current thread inside a new local reference frame.
+///
+/// All local references created by the closure are deleted once it returns, even if it panics.
+///
+/// Once the closure returned, this asserts that `-Xcheck:jni` did not report any warnings, see `JniCheckCollector::assert_no_warnings`.
+/// The collector is process global, so a warning caused by a test running in parallel may also fail this test.
+/// Use `test_vm` and push the local frame yourself to test code that provokes a warning deliberately.
+///
+/// # Panics
+/// If the JVM cannot be loaded, created or attached to or the local frame cannot be pushed.
+/// If `-Xcheck:jni` reported a warning.
+/// Panics from the closure are propagated.
+///
+/// # Safety
+/// Same as `test_vm`.
+///
+pub unsafe fn with_test_frame<R>(f: impl FnOnce(JNIEnv) -> R) -> R {
+    /// Pops the local frame when dropped, this also happens during unwinding.
+    struct FrameGuard(JNIEnv);
+    impl Drop for FrameGuard {
+        fn drop(&mut self) {
+            unsafe {
+                self.0.PopLocalFrame(null_mut());
+            }
+        }
+    }
+
+    let (_, env) = test_vm();
+    assert_eq!(env.PushLocalFrame(TEST_FRAME_CAPACITY), JNI_OK, "PushLocalFrame failed");
+    let guard = FrameGuard(env);
+    let result = f(env);
+    //-Xcheck:jni reports a missing exception check at the next call, which may be PopLocalFrame.
+    drop(guard);
+    COLLECTOR.assert_no_warnings(&[]);
+    result
+}
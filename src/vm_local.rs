@@ -0,0 +1,218 @@
+//!
+//! Container that holds one value per `JavaVM`.
+//!
+
+use crate::{JNIEnv, JavaVM};
+use std::ptr::addr_of;
+use std::sync::{PoisonError, RwLock};
+
+///
+/// Holds one value per `JavaVM`, for caches of global references, method ids or field ids.
+///
+/// Such ids and references are only valid in the JVM that created them. `HotSpot` only supports one JVM per process,
+/// but the JNI specification permits several, so a library that caches them in a static is only strictly correct
+/// if it keys the cache by `JavaVM`.
+///
+/// The values are stored in a small Vec, as a process rarely has more than one JVM.
+/// Values are never removed while the container is shared, so references returned by `get_or_init` stay valid.
+///
+/// # Example
+/// ```rust
+/// use jni_simple::*;
+/// use std::ffi::c_void;
+/// use sync_ptr::SyncMutPtr;
+///
+/// static STRING_CLASS: VmLocal<SyncMutPtr<c_void>> = VmLocal::new();
+///
+/// unsafe fn string_class(env: JNIEnv) -> jclass {
+///     STRING_CLASS
+///         .get_or_init(&env, |env| {
+///             let class = env.FindClass("java/lang/String");
+///             let global = env.NewGlobalRef(class);
+///             env.DeleteLocalRef(class);
+///             SyncMutPtr::new(global)
+///         })
+///         .inner()
+/// }
+/// ```
+///
+#[derive(Debug)]
+pub struct VmLocal<T> {
+    /// The values and the `JavaVM` they belong to.
+    /// The values are boxed so references to them stay valid when the Vec grows.
+    values: RwLock<Vec<(JavaVM, Box<T>)>>,
+}
+
+impl<T> Default for VmLocal<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> VmLocal<T> {
+    ///
+    /// Creates an empty container.
+    ///
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { values: RwLock::new(Vec::new()) }
+    }
+
+    ///
+    /// Returns the value of `vm` or None if there is none yet.
+    ///
+    pub fn get(&self, vm: &JavaVM) -> Option<&T> {
+        let value: *const T = {
+            let values = self.values.read().unwrap_or_else(PoisonError::into_inner);
+            addr_of!(*values.iter().find(|(owner, _)| owner.ptr_eq(vm))?.1)
+        };
+        //SAFETY: The value is boxed and only removed by functions that take &mut self, so it lives as long as &self.
+        Some(unsafe { &*value })
+    }
+
+    ///
+    /// Returns the value of the `JavaVM` that `env` belongs to. If there is none yet, it is created by calling `init`.
+    ///
+    /// `init` is called without holding a lock, so it may use this container for other `JavaVM`s.
+    /// If two threads initialize the value of the same `JavaVM` at the same time, both call `init`,
+    /// but only the value of the first one to finish is kept and returned to both. The other value is dropped.
+    ///
+    /// # Panics
+    /// if `GetJavaVM` fails.
+    /// if asserts feature is enabled and UB was detected
+    ///
+    /// # Safety
+    /// Current thread must not be detached from JNI.
+    ///
+    /// Current thread must not be currently throwing an exception.
+    ///
+    /// Current thread does not hold a critical reference.
+    /// * <https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/functions.html#GetPrimitiveArrayCritical_ReleasePrimitiveArrayCritical>
+    ///
+    pub unsafe fn get_or_init(&self, env: &JNIEnv, init: impl FnOnce(&JNIEnv) -> T) -> &T {
+        let vm = env.owning_vm().expect("GetJavaVM failed");
+        self.get_or_init_with_vm(vm, || init(env))
+    }
+
+    ///
+    /// Returns the value of `vm`. If there is none yet, it is created by calling `init`.
+    ///
+    /// Behaves like `get_or_init` for callers that already know the `JavaVM`.
+    ///
+    pub fn get_or_init_with_vm(&self, vm: JavaVM, init: impl FnOnce() -> T) -> &T {
+        if let Some(value) = self.get(&vm) {
+            return value;
+        }
+
+        let created = Box::new(init());
+        let mut values = self.values.write().unwrap_or_else(PoisonError::into_inner);
+        let value: *const T = if let Some((_, existing)) = values.iter().find(|(owner, _)| owner.ptr_eq(&vm)) {
+            addr_of!(**existing)
+        } else {
+            values.push((vm, created));
+            addr_of!(*values[values.len() - 1].1)
+        };
+        drop(values);
+
+        //SAFETY: The value is boxed and only removed by functions that take &mut self, so it lives as long as &self.
+        unsafe { &*value }
+    }
+
+    ///
+    /// Removes and returns the value of `vm`, i.e. after the `JavaVM` was destroyed.
+    ///
+    pub fn remove(&mut self, vm: &JavaVM) -> Option<T> {
+        let values = self.values.get_mut().unwrap_or_else(PoisonError::into_inner);
+        let index = values.iter().position(|(owner, _)| owner.ptr_eq(vm))?;
+        Some(*values.swap_remove(index).1)
+    }
+
+    ///
+    /// Returns the amount of `JavaVM`s that have a value.
+    ///
+    pub fn len(&self) -> usize {
+        self.values.read().unwrap_or_else(PoisonError::into_inner).len()
+    }
+
+    ///
+    /// Returns true if no `JavaVM` has a value.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Creates a `JavaVM` from a fake pointer value. The `JavaVM` is only compared, never used.
+#[cfg(test)]
+const fn mock_vm(value: usize) -> JavaVM {
+    unsafe { JavaVM::from_raw_ptr(value as *mut std::ffi::c_void) }
+}
+
+#[cfg(test)]
+#[test]
+fn test_get_or_init() {
+    let local = VmLocal::new();
+    assert!(local.is_empty());
+    assert_eq!(local.get(&mock_vm(0x1000)), None);
+
+    let first = local.get_or_init_with_vm(mock_vm(0x1000), || String::from("first"));
+    assert_eq!(first, "first");
+    let second = local.get_or_init_with_vm(mock_vm(0x2000), || String::from("second"));
+    assert_eq!(second, "second");
+    assert_eq!(local.len(), 2);
+
+    assert_eq!(local.get_or_init_with_vm(mock_vm(0x1000), || unreachable!("value exists")), "first");
+    assert_eq!(local.get(&mock_vm(0x2000)).map(String::as_str), Some("second"));
+    assert_eq!(local.get(&mock_vm(0x3000)), None);
+
+    //References stay valid when the Vec grows.
+    for i in 0..64 {
+        local.get_or_init_with_vm(mock_vm(0x10_0000 + i * 8), || i.to_string());
+    }
+    assert_eq!(first, "first");
+    assert_eq!(second, "second");
+    assert_eq!(local.len(), 66);
+}
+
+#[cfg(test)]
+#[test]
+fn test_remove() {
+    let mut local = VmLocal::new();
+    local.get_or_init_with_vm(mock_vm(0x1000), || 1);
+    local.get_or_init_with_vm(mock_vm(0x2000), || 2);
+    assert_eq!(local.remove(&mock_vm(0x1000)), Some(1));
+    assert_eq!(local.remove(&mock_vm(0x1000)), None);
+    assert_eq!(local.len(), 1);
+    assert_eq!(local.get(&mock_vm(0x2000)), Some(&2));
+    assert_eq!(local.get_or_init_with_vm(mock_vm(0x1000), || 3), &3);
+}
+
+#[cfg(test)]
+#[test]
+fn test_concurrent_init() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Barrier;
+
+    let local = VmLocal::new();
+    let calls = AtomicUsize::new(0);
+    let barrier = Barrier::new(8);
+    let (local, calls, barrier) = (&local, &calls, &barrier);
+    let values = std::thread::scope(|scope| {
+        let mut handles = Vec::new();
+        for i in 0..8 {
+            handles.push(scope.spawn(move || {
+                barrier.wait();
+                let value: &usize = local.get_or_init_with_vm(mock_vm(0x1000), || {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    i
+                });
+                std::ptr::from_ref(value) as usize
+            }));
+        }
+        handles.into_iter().map(|handle| handle.join().expect("thread panicked")).collect::<Vec<_>>()
+    });
+
+    assert!(calls.load(Ordering::SeqCst) >= 1);
+    assert_eq!(local.len(), 1);
+    assert!(values.iter().all(|value| *value == values[0]));
+}
@@ -0,0 +1,40 @@
+#[cfg(all(feature = "test-util", feature = "asserts"))]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null;
+    use std::sync::Mutex;
+
+    static FAILURES: Mutex<Vec<AssertFailure>> = Mutex::new(Vec::new());
+
+    fn record(failure: &AssertFailure) {
+        FAILURES.lock().unwrap().push(failure.clone());
+    }
+
+    #[test]
+    fn test() {
+        unsafe {
+            let (_, env) = testing::test_vm();
+            set_assert_failure_handler(record);
+
+            let result = std::panic::catch_unwind(|| env.FindClass(null::<u8>()));
+            assert!(result.is_err());
+
+            //The message of this check does not start with the name of the function that detected it.
+            let unterminated = [b'a'; 16];
+            set_assert_scan_limit(8);
+            let result = std::panic::catch_unwind(|| env.FindClass(unterminated.as_ptr()));
+            set_assert_scan_limit(1024 * 1024);
+            assert!(result.is_err());
+
+            let failures = FAILURES.lock().unwrap();
+            assert_eq!(failures.len(), 2);
+            assert_eq!(failures[1].function(), "use_as_const_c_char");
+            assert!(failures[1].message().starts_with("string pointer is not 0 terminated"));
+            assert_eq!(failures[0].function(), "FindClass");
+            assert_eq!(failures[0].message(), "FindClass name is null");
+            assert_eq!(failures[0].thread(), std::thread::current().id());
+            assert!(!failures[0].is_warning());
+            assert_eq!(failures[0].to_string(), "FindClass name is null");
+        }
+    }
+}
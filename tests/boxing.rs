@@ -0,0 +1,74 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_round_trip() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                for value in [true, false] {
+                    assert_eq!(env.unbox_boolean(env.box_boolean(value)), Some(value));
+                }
+                for value in [jbyte::MIN, -1, 0, 1, jbyte::MAX] {
+                    assert_eq!(env.unbox_byte(env.box_byte(value)), Some(value));
+                }
+                for value in [jchar::MIN, 'a' as jchar, jchar::MAX] {
+                    assert_eq!(env.unbox_char(env.box_char(value)), Some(value));
+                }
+                for value in [jshort::MIN, 0, jshort::MAX] {
+                    assert_eq!(env.unbox_short(env.box_short(value)), Some(value));
+                }
+                for value in [jint::MIN, -128, 127, 1000, jint::MAX] {
+                    assert_eq!(env.unbox_int(env.box_int(value)), Some(value));
+                }
+                for value in [jlong::MIN, 0, jlong::MAX] {
+                    assert_eq!(env.unbox_long(env.box_long(value)), Some(value));
+                }
+                for value in [jfloat::MIN, -0.0, jfloat::INFINITY, jfloat::NAN, jfloat::MAX] {
+                    assert_eq!(env.unbox_float(env.box_float(value)).map(jfloat::to_bits), Some(value.to_bits()), "{value}");
+                }
+                for value in [jdouble::MIN, -0.0, jdouble::NEG_INFINITY, jdouble::NAN, jdouble::MAX] {
+                    assert_eq!(env.unbox_double(env.box_double(value)).map(jdouble::to_bits), Some(value.to_bits()));
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_wrong_type() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let boxed = env.box_long(5);
+                assert_eq!(env.unbox_int(boxed), None);
+                assert_eq!(env.unbox_long(boxed), Some(5));
+                assert_eq!(env.unbox_int(null_mut()), None);
+                let string = env.NewStringUTF("5");
+                assert_eq!(env.unbox_double(string), None);
+            });
+        }
+    }
+
+    #[test]
+    fn test_int_array() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let values = [jint::MIN, 0, 42, jint::MAX];
+                let array = env.new_boxed_int_array(&values);
+                assert!(!array.is_null());
+                assert_eq!(env.GetArrayLength(array), 4);
+                let integer_class = env.FindClass("java/lang/Integer");
+                let array_class = env.GetObjectClass(array);
+                let get_component_type = env.GetMethodID(env.GetObjectClass(array_class), "getComponentType", "()Ljava/lang/Class;");
+                let component = env.CallObjectMethod0(array_class, get_component_type);
+                assert!(!env.ExceptionCheck());
+                assert!(env.IsSameObject(component, integer_class));
+
+                for (index, value) in values.iter().enumerate() {
+                    let element = env.GetObjectArrayElement(array, index as jsize);
+                    assert_eq!(env.unbox_int(element), Some(*value));
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null;
+
+    #[test]
+    fn test_static() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let thread = env.FindClass("java/lang/Thread");
+                let yield_method = env.GetStaticMethodID(thread, "yield", "()V");
+                assert!(!env.CallStaticVoidMethodA_and_clear(thread, yield_method, null()));
+                assert!(!env.ExceptionCheck());
+
+                let system = env.FindClass("java/lang/System");
+                let array_copy = env.GetStaticMethodID(system, "arraycopy", "(Ljava/lang/Object;ILjava/lang/Object;II)V");
+                assert!(env.CallStaticVoidMethodA_and_clear(system, array_copy, jtypes!(null, 0i32, null, 0i32, 1i32).as_ptr()));
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_instance() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let list_class = env.FindClass("java/util/ArrayList");
+                let constructor = env.GetMethodID(list_class, "<init>", "()V");
+                let add = env.GetMethodID(list_class, "add", "(ILjava/lang/Object;)V");
+                let size = env.GetMethodID(list_class, "size", "()I");
+                let list = env.NewObject0(list_class, constructor);
+                let element = env.NewStringUTF("element");
+
+                assert!(!env.CallVoidMethodA_and_clear(list, add, jtypes!(0i32, element).as_ptr()));
+                assert!(!env.ExceptionCheck());
+                assert!(env.CallVoidMethodA_and_clear(list, add, jtypes!(5i32, element).as_ptr()));
+                assert!(!env.ExceptionCheck());
+                assert_eq!(env.CallIntMethod0(list, size), 1);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    unsafe fn names(env: JNIEnv, classes: Vec<jclass>) -> Vec<String> {
+        classes
+            .into_iter()
+            .map(|class| {
+                let name = env.get_class_name(class).expect("get_class_name failed");
+                env.DeleteLocalRef(class);
+                name
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_array_list() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = env.FindClass("java/util/ArrayList");
+                let hierarchy = names(env, env.class_hierarchy(class));
+                assert_eq!(
+                    hierarchy,
+                    ["java.util.ArrayList", "java.util.AbstractList", "java.util.AbstractCollection", "java.lang.Object"]
+                );
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_object_and_interface() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = env.FindClass("java/lang/Object");
+                assert_eq!(names(env, env.class_hierarchy(class)), ["java.lang.Object"]);
+
+                let class = env.FindClass("java/util/List");
+                assert_eq!(names(env, env.class_hierarchy(class)), ["java.util.List"]);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_full_type_closure() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = env.FindClass("java/util/ArrayList");
+                let closure = names(env, env.full_type_closure(class));
+                assert_eq!(
+                    closure[..4],
+                    ["java.util.ArrayList", "java.util.AbstractList", "java.util.AbstractCollection", "java.lang.Object"]
+                );
+                for interface in ["java.util.List", "java.util.Collection", "java.lang.Iterable", "java.util.RandomAccess"] {
+                    assert_eq!(closure.iter().filter(|name| *name == interface).count(), 1, "{interface} in {closure:?}");
+                }
+                //Iterable is only implemented through Collection.
+                let position = |name: &str| closure.iter().position(|known| known == name).expect("not found");
+                assert!(position("java.util.Collection") < position("java.lang.Iterable"));
+
+                let class = env.FindClass("java/util/List");
+                let closure = names(env, env.full_type_closure(class));
+                assert_eq!(closure[0], "java.util.List");
+                assert!(closure.iter().any(|name| name == "java.util.Collection"));
+                assert!(closure.iter().any(|name| name == "java.lang.Iterable"));
+                assert!(!closure.iter().any(|name| name == "java.lang.Object"));
+
+                let class = env.FindClass("java/lang/Object");
+                assert_eq!(names(env, env.full_type_closure(class)), ["java.lang.Object"]);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_class_name() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string_class = env.FindClass("java/lang/String");
+                assert_eq!(env.get_class_name(string_class).as_deref(), Some("java.lang.String"));
+
+                let entry_class = env.FindClass("java/util/Map$Entry");
+                assert_eq!(env.get_class_name(entry_class).as_deref(), Some("java.util.Map$Entry"));
+
+                let int_array = env.NewIntArray(3);
+                assert_eq!(env.get_object_class_name(int_array).as_deref(), Some("[I"));
+
+                let string_array = env.NewObjectArray(1, string_class, null_mut());
+                assert_eq!(env.get_object_class_name(string_array).as_deref(), Some("[Ljava.lang.String;"));
+
+                let string = env.NewStringUTF("hello");
+                assert_eq!(env.get_object_class_name(string).as_deref(), Some("java.lang.String"));
+
+                assert_eq!(env.get_class_name(null_mut()), None);
+                assert_eq!(env.get_object_class_name(null_mut()), None);
+            });
+        }
+    }
+
+    #[test]
+    fn test_pending_exception() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string_class = env.FindClass("java/lang/String");
+                let exception_class = env.FindClass("java/lang/RuntimeException");
+                assert_eq!(env.ThrowNew(exception_class, "pending"), JNI_OK);
+                assert_eq!(env.get_class_name(string_class), None);
+                assert_eq!(env.get_object_class_name(string_class), None);
+                assert!(env.ExceptionCheck());
+                env.ExceptionClear();
+                assert_eq!(env.get_object_class_name(string_class).as_deref(), Some("java.lang.Class"));
+            });
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use jni_simple::class_name::{self, ClassNameError};
+
+#[test]
+fn test_validate() {
+    for valid in [
+        "java/lang/String",
+        "java/util/Map$Entry",
+        "Foo",
+        "I",
+        "[I",
+        "[[D",
+        "[Ljava/lang/String;",
+        "[[Ljava/util/Map$Entry;",
+    ] {
+        assert_eq!(class_name::validate(valid), Ok(()), "{valid}");
+    }
+
+    let mistakes = [
+        ("", ClassNameError::Empty, "is empty"),
+        ("java.lang.String", ClassNameError::ContainsDot, "contains '.', did you mean '/'"),
+        ("[Ljava.lang.String;", ClassNameError::ContainsDot, "contains '.', did you mean '/'"),
+        (
+            "Ljava/lang/String;",
+            ClassNameError::FieldDescriptor,
+            "looks like a field descriptor (Lxxx;), pass the plain internal name",
+        ),
+        (
+            "Ljava.lang.String;",
+            ClassNameError::FieldDescriptor,
+            "looks like a field descriptor (Lxxx;), pass the plain internal name",
+        ),
+        (
+            "java/lang/String[]",
+            ClassNameError::ArrayNotDescriptor,
+            "array classes must use descriptor form like [Ljava/lang/String;",
+        ),
+        (
+            "[java/lang/String",
+            ClassNameError::ArrayNotDescriptor,
+            "array classes must use descriptor form like [Ljava/lang/String;",
+        ),
+        (
+            "[Ljava/lang/String",
+            ClassNameError::ArrayNotDescriptor,
+            "array classes must use descriptor form like [Ljava/lang/String;",
+        ),
+        (
+            "[int",
+            ClassNameError::ArrayNotDescriptor,
+            "array classes must use descriptor form like [Ljava/lang/String;",
+        ),
+        ("[", ClassNameError::ArrayNotDescriptor, "array classes must use descriptor form like [Ljava/lang/String;"),
+        ("java/lang\0/String", ClassNameError::ContainsNul, "contains a NUL character"),
+        ("java/lang/String ", ClassNameError::ContainsWhitespace, "contains whitespace"),
+        ("java/lang/\tString", ClassNameError::ContainsWhitespace, "contains whitespace"),
+    ];
+
+    for (name, error, message) in mistakes {
+        assert_eq!(class_name::validate(name), Err(error), "{name:?}");
+        assert_eq!(error.to_string(), message);
+    }
+}
+
+#[test]
+fn test_conversion() {
+    assert_eq!(class_name::to_internal("java.lang.String"), "java/lang/String");
+    assert_eq!(class_name::to_internal("java/lang/String"), "java/lang/String");
+    assert_eq!(class_name::to_internal("Ljava/lang/String;"), "java/lang/String");
+    assert_eq!(class_name::to_internal("Ljava.lang.String;"), "java/lang/String");
+    assert_eq!(class_name::to_internal("[Ljava.lang.String;"), "[Ljava/lang/String;");
+    assert_eq!(class_name::to_internal("[I"), "[I");
+    assert!(matches!(class_name::to_internal("java/lang/String"), std::borrow::Cow::Borrowed(_)));
+
+    assert_eq!(class_name::to_binary("java/lang/String"), "java.lang.String");
+    assert_eq!(class_name::to_binary("java.lang.String"), "java.lang.String");
+    assert_eq!(class_name::to_binary("Ljava/lang/String;"), "java.lang.String");
+    assert_eq!(class_name::to_binary("[Ljava/lang/String;"), "[Ljava.lang.String;");
+    assert_eq!(class_name::to_binary("java/util/Map$Entry"), "java.util.Map$Entry");
+    assert!(matches!(class_name::to_binary("java.lang.String"), std::borrow::Cow::Borrowed(_)));
+
+    assert_eq!(class_name::validate(&class_name::to_internal("java.util.Map$Entry")), Ok(()));
+}
+
+#[cfg(all(feature = "test-util", feature = "asserts"))]
+mod jvm {
+    use jni_simple::*;
+
+    fn panic_message(f: impl FnOnce()) -> String {
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).expect_err("expected a panic");
+        payload.downcast_ref::<String>().expect("panic payload is not a String").clone()
+    }
+
+    #[test]
+    fn test_find_class() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let message = panic_message(|| {
+                    env.FindClass("java.lang.String");
+                });
+                assert_eq!(message, "FindClass class name java.lang.String contains '.', did you mean '/'");
+
+                let message = panic_message(|| {
+                    env.FindClass("Ljava/lang/String;");
+                });
+                assert_eq!(
+                    message,
+                    "FindClass class name Ljava/lang/String; looks like a field descriptor (Lxxx;), pass the plain internal name"
+                );
+
+                let message = panic_message(|| {
+                    env.DefineClass_from_slice("some.Class", std::ptr::null_mut(), [0u8; 4]);
+                });
+                assert_eq!(message, "DefineClass class name some.Class contains '.', did you mean '/'");
+
+                assert!(!env.FindClass("[Ljava/lang/String;").is_null());
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
@@ -0,0 +1,56 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_null() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                assert_eq!(env.debug_validate_ref(null_mut()), RefValidity::Null);
+            });
+        }
+    }
+
+    #[cfg(not(feature = "asserts"))]
+    #[test]
+    fn test_unchecked() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let local = env.NewStringUTF("local");
+                assert_eq!(env.debug_validate_ref(local), RefValidity::Unchecked);
+            });
+        }
+    }
+
+    #[cfg(feature = "asserts")]
+    #[test]
+    fn test_valid() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let local = env.NewStringUTF("local");
+                assert_eq!(env.debug_validate_ref(local), RefValidity::Valid(jobjectRefType::JNILocalRefType));
+
+                let global = env.NewGlobalRef(local);
+                assert_eq!(env.debug_validate_ref(global), RefValidity::Valid(jobjectRefType::JNIGlobalRefType));
+                env.DeleteGlobalRef(global);
+
+                let weak = env.NewWeakGlobalRef(local);
+                assert_eq!(env.debug_validate_ref(weak), RefValidity::Valid(jobjectRefType::JNIWeakGlobalRefType));
+                env.DeleteWeakGlobalRef(weak);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[cfg(feature = "asserts")]
+    #[test]
+    fn test_misaligned() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let misaligned = 0x1004 as jobject;
+                assert_eq!(env.debug_validate_ref(misaligned), RefValidity::Suspicious("pointer is not aligned"));
+            });
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//-Xcheck:jni aborts the process on the invalid references these tests pass on purpose,
+//so they create their own JVM without it instead of using the test harness.
+#[cfg(all(feature = "loadjvm", feature = "asserts"))]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test_invalid_refs() {
+        unsafe {
+            load_jvm_from_java_home().expect("failed to load jvm");
+            let args: Vec<String> = vec![];
+            let (_, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &args).expect("failed to create jvm");
+
+            let local = env.NewStringUTF("deleted");
+            env.DeleteLocalRef(local);
+            assert!(matches!(env.debug_validate_ref(local), RefValidity::Suspicious(_)));
+
+            //What the JVM does with a garbage pointer is specific to the JVM, so this only runs if requested.
+            if std::env::var_os("JNI_SIMPLE_TEST_GARBAGE_REF").is_some() {
+                let garbage = Box::new([0xDEAD_BEEF_usize; 4]);
+                let result = env.debug_validate_ref(garbage.as_ptr().cast_mut().cast());
+                assert!(matches!(result, RefValidity::Suspicious(_)), "{result:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class_blob = include_bytes!("../java_testcode/RegisterTest.class");
+                let dir = std::env::temp_dir();
+                let full_path = dir.join(format!("jni_simple_define_class_file_{}_full.class", std::process::id()));
+                let truncated_path = dir.join(format!("jni_simple_define_class_file_{}_truncated.class", std::process::id()));
+                let missing_path = dir.join(format!("jni_simple_define_class_file_{}_missing.class", std::process::id()));
+                std::fs::write(&full_path, class_blob).unwrap();
+                std::fs::write(&truncated_path, &class_blob[..class_blob.len() / 2]).unwrap();
+
+                match env.DefineClass_from_file("RegisterTest", null_mut(), &missing_path) {
+                    Err(DefineClassError::Io(_)) => {}
+                    other => panic!("expected io error got {other:?}"),
+                }
+                assert!(!env.ExceptionCheck());
+
+                match env.DefineClass_from_file("RegisterTest", null_mut(), &truncated_path) {
+                    Err(DefineClassError::JavaException(name)) => assert_eq!(name.as_str(), "RegisterTest"),
+                    other => panic!("expected java exception got {other:?}"),
+                }
+                let exc = env.ExceptionOccurred();
+                assert!(!exc.is_null());
+                env.ExceptionClear();
+                let class_format_error = env.FindClass("java/lang/ClassFormatError");
+                assert!(env.IsInstanceOf(exc, class_format_error));
+                env.DeleteLocalRef(exc);
+                env.DeleteLocalRef(class_format_error);
+
+                let class = env.DefineClass_from_file("RegisterTest", null_mut(), &full_path).expect("failed to define class");
+                assert!(!class.is_null());
+                assert!(!env.ExceptionCheck());
+                env.DeleteLocalRef(class);
+
+                _ = std::fs::remove_file(&full_path);
+                _ = std::fs::remove_file(&truncated_path);
+            });
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                assert_eq!(env.delete_ref(null_mut()), Ok(()));
+
+                let local = env.NewStringUTF("delete_ref");
+                let global = env.NewGlobalRef(local);
+                let weak = env.NewWeakGlobalRef(local);
+                let second_local = env.NewLocalRef(local);
+                assert_eq!(env.GetObjectRefType(second_local), jobjectRefType::JNILocalRefType);
+
+                assert_eq!(env.delete_ref(global), Ok(()));
+                assert_eq!(env.delete_ref(weak), Ok(()));
+                assert_eq!(env.delete_ref(second_local), Ok(()));
+                assert_eq!(env.GetObjectRefType(local), jobjectRefType::JNILocalRefType);
+                assert_eq!(env.delete_ref(local), Ok(()));
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
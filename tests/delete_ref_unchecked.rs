@@ -0,0 +1,44 @@
+//-Xcheck:jni aborts the process on the deleted references this test passes on purpose,
+//so it creates its own JVM without it instead of using the test harness.
+#[cfg(all(feature = "loadjvm", feature = "asserts"))]
+pub mod test {
+    use jni_simple::*;
+    use std::sync::Mutex;
+
+    static WARNINGS: Mutex<Vec<AssertFailure>> = Mutex::new(Vec::new());
+
+    fn record(failure: &AssertFailure) {
+        WARNINGS.lock().unwrap().push(failure.clone());
+    }
+
+    #[test]
+    fn test_already_deleted() {
+        unsafe {
+            load_jvm_from_java_home().expect("failed to load jvm");
+            let args: Vec<String> = vec![];
+            let (_, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &args).expect("failed to create jvm");
+
+            //Deleted references are only recognizable on HotSpot 9 and later, deleting them again is UB on older JVMs.
+            if env.GetVersion() < JNI_VERSION_9 {
+                eprintln!("skipping already deleted test, the JVM does not recognize deleted references");
+                return;
+            }
+
+            set_assert_failure_handler(record);
+            let local = env.NewStringUTF("delete_ref");
+            let global = env.NewGlobalRef(local);
+            env.DeleteGlobalRef(global);
+            assert_eq!(env.delete_ref(global), Err(jobjectRefType::JNIInvalidRefType));
+
+            env.DeleteLocalRef(local);
+            assert_eq!(env.delete_ref(local), Err(jobjectRefType::JNILocalRefType));
+            assert!(!env.ExceptionCheck());
+
+            let warnings = WARNINGS.lock().unwrap();
+            assert_eq!(warnings.len(), 2);
+            assert!(warnings.iter().all(|warning| warning.is_warning() && warning.function() == "delete_ref"));
+            assert!(warnings[0].message().contains("invalid reference"));
+            assert!(warnings[1].message().contains("already deleted"));
+        }
+    }
+}
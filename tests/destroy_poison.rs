@@ -0,0 +1,21 @@
+#[cfg(all(feature = "loadjvm", feature = "asserts"))]
+pub mod test {
+    use jni_simple::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn test() {
+        unsafe {
+            load_jvm_from_java_home().expect("failed to load jvm");
+            let args: Vec<String> = vec![];
+            let (vm, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &args).expect("failed to create java VM");
+            assert!(env.GetVersion() >= JNI_VERSION_1_8);
+
+            assert_eq!(vm.DestroyJavaVM(), DestroyJavaVMResult::Ok);
+
+            let payload = catch_unwind(AssertUnwindSafe(|| env.GetVersion())).expect_err("GetVersion after DestroyJavaVM did not panic");
+            let message = payload.downcast_ref::<String>().expect("panic payload is not a String");
+            assert_eq!(message, "JNI call after DestroyJavaVM");
+        }
+    }
+}
@@ -0,0 +1,48 @@
+#[cfg(feature = "loadjvm")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test() {
+        unsafe {
+            load_jvm_from_java_home().expect("failed to load jvm");
+            let args: Vec<String> = vec![];
+            let (vm, _env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &args).expect("failed to create java VM");
+
+            let mut handles = Vec::new();
+            for _ in 0..4 {
+                handles.push(std::thread::spawn(move || {
+                    let env = vm.AttachCurrentThread_str(JNI_VERSION_1_8, None, null_mut()).unwrap();
+                    vm.ensure_detach_on_thread_exit();
+                    vm.ensure_detach_on_thread_exit();
+                    let class = env.FindClass("java/lang/Object");
+                    assert!(!class.is_null());
+                    env.DeleteLocalRef(class);
+                }));
+            }
+
+            for handle in handles {
+                handle.join().unwrap();
+            }
+
+            //Never attached, must not do anything.
+            std::thread::spawn(move || vm.ensure_detach_on_thread_exit()).join().unwrap();
+
+            //Detached manually, must not detach again.
+            std::thread::spawn(move || {
+                vm.AttachCurrentThread_str(JNI_VERSION_1_8, None, null_mut()).unwrap();
+                vm.ensure_detach_on_thread_exit();
+                assert_eq!(JNI_OK, vm.DetachCurrentThread());
+            })
+            .join()
+            .unwrap();
+
+            //This would block forever if any of the threads above was still attached.
+            vm.DestroyJavaVM();
+
+            //Thread that exits after the JVM is gone.
+            std::thread::spawn(move || vm.ensure_detach_on_thread_exit()).join().unwrap();
+        }
+    }
+}
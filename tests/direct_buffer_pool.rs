@@ -0,0 +1,58 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let pool = DirectBufferPool::new();
+                assert!(pool.is_empty());
+
+                let mut buffer = pool.acquire(&env, 16).expect("acquire failed");
+                assert!(!env.ExceptionCheck());
+                assert_eq!(buffer.capacity(), 16);
+                assert_eq!(env.GetObjectRefType(buffer.java_buffer()), jobjectRefType::JNIGlobalRefType);
+                assert_eq!(env.GetDirectBufferCapacity(buffer.java_buffer()), 16);
+                assert_eq!(env.GetDirectBufferAddress(buffer.java_buffer()).cast::<u8>().cast_const(), buffer.as_slice().as_ptr());
+
+                buffer.as_mut_slice()[3] = 42;
+                let byte_buffer_class = env.FindClass("java/nio/ByteBuffer");
+                let get = env.GetMethodID(byte_buffer_class, "get", "(I)B");
+                let position = env.GetMethodID(byte_buffer_class, "position", "(I)Ljava/nio/Buffer;");
+                assert_eq!(env.CallByteMethod1(buffer.java_buffer(), get, 3), 42);
+                assert!(!env.ExceptionCheck());
+                let result = env.CallObjectMethod1(buffer.java_buffer(), position, 7);
+                assert!(!env.ExceptionCheck());
+                env.DeleteLocalRef(result);
+
+                let java_buffer = buffer.java_buffer();
+                drop(buffer);
+                assert_eq!(pool.len(), 1);
+
+                let smaller = pool.acquire(&env, 8).expect("acquire failed");
+                assert!(pool.is_empty());
+                assert!(env.IsSameObject(smaller.java_buffer(), java_buffer));
+                assert_eq!(smaller.as_slice()[3], 42);
+                let get_position = env.GetMethodID(byte_buffer_class, "position", "()I");
+                assert_eq!(env.CallIntMethod0(smaller.java_buffer(), get_position), 0);
+                assert!(!env.ExceptionCheck());
+
+                let larger = pool.acquire(&env, 32).expect("acquire failed");
+                assert_eq!(larger.capacity(), 32);
+                assert!(!env.IsSameObject(larger.java_buffer(), java_buffer));
+                drop(smaller);
+                drop(larger);
+                assert_eq!(pool.len(), 2);
+
+                let empty = pool.acquire(&env, 0).expect("acquire failed");
+                assert_eq!(empty.capacity(), 16);
+                drop(empty);
+
+                pool.destroy(&env);
+                assert!(pool.is_empty());
+                env.DeleteLocalRef(byte_buffer_class);
+            });
+        }
+    }
+}
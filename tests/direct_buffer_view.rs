@@ -0,0 +1,101 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test_sliced() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let mut memory = [0u8; 64];
+                for (idx, b) in memory.iter_mut().enumerate() {
+                    *b = idx as u8;
+                }
+                let buf = env.NewDirectByteBuffer(memory.as_mut_ptr().cast(), memory.len() as jlong);
+                assert!(!buf.is_null());
+
+                let view = env.direct_buffer_view(buf).expect("not a direct buffer");
+                assert_eq!(view.full_slice_ptr(), memory.as_ptr());
+                assert_eq!(view.capacity(), 64);
+                assert_eq!(view.position(), 0);
+                assert_eq!(view.remaining_len(), 64);
+                assert!(!view.is_read_only());
+                assert_eq!(view.full_slice_mut_ptr(), Some(memory.as_mut_ptr()));
+
+                let buffer_class = env.FindClass("java/nio/ByteBuffer");
+                let position = env.GetMethodID(buffer_class, "position", "(I)Ljava/nio/Buffer;");
+                let limit = env.GetMethodID(buffer_class, "limit", "(I)Ljava/nio/Buffer;");
+                let slice = env.GetMethodID(buffer_class, "slice", "()Ljava/nio/ByteBuffer;");
+                let as_read_only = env.GetMethodID(buffer_class, "asReadOnlyBuffer", "()Ljava/nio/ByteBuffer;");
+
+                env.CallObjectMethod1(buf, position, 8);
+                assert!(!env.ExceptionCheck());
+                env.CallObjectMethod1(buf, limit, 40);
+                assert!(!env.ExceptionCheck());
+
+                let view = env.direct_buffer_view(buf).expect("not a direct buffer");
+                assert_eq!(view.full_slice_ptr(), memory.as_ptr());
+                assert_eq!(view.capacity(), 64);
+                assert_eq!(view.position(), 8);
+                assert_eq!(view.limit(), 40);
+                assert_eq!(view.remaining_len(), 32);
+                assert_eq!(*view.remaining_slice_ptr(), 8);
+
+                //A slice starts at the position of its parent.
+                let sliced = env.CallObjectMethod0(buf, slice);
+                assert!(!env.ExceptionCheck());
+                env.CallObjectMethod1(sliced, position, 4);
+                assert!(!env.ExceptionCheck());
+                let view = env.direct_buffer_view(sliced).expect("not a direct buffer");
+                assert_eq!(view.full_slice_ptr(), memory.as_ptr().add(8));
+                assert_eq!(view.capacity(), 32);
+                assert_eq!(view.remaining_len(), 28);
+                let remaining = std::slice::from_raw_parts(view.remaining_slice_ptr(), view.remaining_len());
+                assert_eq!(remaining, &memory[12..40]);
+
+                let read_only = env.CallObjectMethod0(sliced, as_read_only);
+                assert!(!env.ExceptionCheck());
+                let view = env.direct_buffer_view(read_only).expect("not a direct buffer");
+                assert!(view.is_read_only());
+                assert_eq!(view.full_slice_mut_ptr(), None);
+                assert_eq!(view.remaining_slice_mut_ptr(), None);
+                assert_eq!(view.remaining_slice_ptr(), memory.as_ptr().add(12));
+            });
+        }
+    }
+
+    #[test]
+    fn test_heap_buffer() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let buffer_class = env.FindClass("java/nio/ByteBuffer");
+                let allocate = env.GetStaticMethodID(buffer_class, "allocate", "(I)Ljava/nio/ByteBuffer;");
+                let heap = env.CallStaticObjectMethod1(buffer_class, allocate, 16);
+                assert!(!env.ExceptionCheck());
+                assert!(!heap.is_null());
+                assert!(env.direct_buffer_view(heap).is_none());
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_int_buffer() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let mut memory = [0u8; 64];
+                let buf = env.NewDirectByteBuffer(memory.as_mut_ptr().cast(), memory.len() as jlong);
+                assert!(!buf.is_null());
+
+                //Position and limit of an IntBuffer count ints, not bytes.
+                let buffer_class = env.FindClass("java/nio/ByteBuffer");
+                let as_int_buffer = env.GetMethodID(buffer_class, "asIntBuffer", "()Ljava/nio/IntBuffer;");
+                let int_buffer = env.CallObjectMethod0(buf, as_int_buffer);
+                assert!(!env.ExceptionCheck());
+                assert!(!int_buffer.is_null());
+                assert_eq!(env.GetDirectBufferAddress(int_buffer), memory.as_mut_ptr().cast());
+                assert!(env.direct_buffer_view(int_buffer).is_none());
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
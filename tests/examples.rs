@@ -0,0 +1,61 @@
+//! Runs the examples that cargo built alongside the tests.
+//! Examples that were not built (i.e. because their required features are disabled) and runs without a `JAVA_HOME` are skipped.
+
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX, EXE_SUFFIX};
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn examples_dir() -> PathBuf {
+    let exe = std::env::current_exe().expect("current_exe");
+    exe.parent()
+        .and_then(|deps| deps.parent())
+        .expect("test binary is not in target/<profile>/deps")
+        .join("examples")
+}
+
+fn java_home() -> Option<PathBuf> {
+    std::env::var_os("JAVA_HOME").map(PathBuf::from).filter(|home| home.join("bin").is_dir())
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "example failed with {}\nstdout:\n{}\nstderr:\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+#[test]
+fn test_embed() {
+    let example = examples_dir().join(format!("embed{EXE_SUFFIX}"));
+    if java_home().is_none() || !example.is_file() {
+        eprintln!("skipping, JAVA_HOME is not set or {} was not built", example.display());
+        return;
+    }
+
+    let output = stdout(&Command::new(example).output().expect("failed to run embed example"));
+    assert!(output.contains("EmbedExample.main called with hello,from,rust\n"), "{output}");
+    assert!(output.contains("JVM destroyed: Ok\n"), "{output}");
+}
+
+#[test]
+fn test_native_library() {
+    let library = examples_dir().join(format!("{DLL_PREFIX}native_library{DLL_SUFFIX}"));
+    let Some(java_home) = java_home().filter(|_| library.is_file()) else {
+        eprintln!("skipping, JAVA_HOME is not set or {} was not built", library.display());
+        return;
+    };
+
+    let output = Command::new(java_home.join("bin").join(format!("java{EXE_SUFFIX}")))
+        .arg("-cp")
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/java_testcode"))
+        .arg("NativeLibraryExample")
+        .arg(library)
+        .output()
+        .expect("failed to run java");
+    let output = stdout(&output);
+    assert_eq!(output.lines().collect::<Vec<_>>(), ["Hello java from rust", "10", "a|b|c"]);
+}
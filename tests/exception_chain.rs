@@ -0,0 +1,97 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe fn new_exception(env: JNIEnv, class: &str, message: Option<&str>, cause: jthrowable) -> jthrowable {
+        let class = env.FindClass(class);
+        let constructor = env.GetMethodID(class, "<init>", "(Ljava/lang/String;Ljava/lang/Throwable;)V");
+        let message = message.map_or(null_mut(), |message| env.NewStringUTF(message));
+        let exception = env.NewObjectA(class, constructor, jtypes!(message, cause).as_ptr());
+        assert!(!env.ExceptionCheck());
+        assert!(!exception.is_null());
+        exception
+    }
+
+    fn info(chain: &[ExceptionInfo]) -> Vec<(&str, Option<&str>)> {
+        chain.iter().map(|info| (info.class_name(), info.message())).collect()
+    }
+
+    #[test]
+    fn test_chain() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let root = new_exception(env, "java/lang/IllegalStateException", Some("root"), null_mut());
+                let middle = new_exception(env, "java/lang/RuntimeException", None, root);
+                let top = new_exception(env, "java/lang/Exception", Some("top"), middle);
+
+                let chain = env.get_exception_chain(top, 32);
+                assert_eq!(
+                    info(&chain),
+                    vec![
+                        ("java.lang.Exception", Some("top")),
+                        ("java.lang.RuntimeException", None),
+                        ("java.lang.IllegalStateException", Some("root")),
+                    ]
+                );
+                assert_eq!(chain[0].to_string(), "java.lang.Exception: top");
+                assert_eq!(chain[1].to_string(), "java.lang.RuntimeException");
+
+                assert_eq!(
+                    info(&env.get_exception_chain(top, 2)),
+                    vec![("java.lang.Exception", Some("top")), ("java.lang.RuntimeException", None)]
+                );
+                assert_eq!(env.get_exception_chain(top, usize::MAX).len(), 3);
+                assert!(!env.ExceptionCheck());
+                assert!(env.get_exception_chain(top, 0).is_empty());
+                assert!(env.get_exception_chain(null_mut(), 32).is_empty());
+
+                //The caller's reference is still usable.
+                assert!(!env.IsSameObject(top, null_mut()));
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_deep() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                //new_exception leaks 3 local references per exception.
+                assert_eq!(env.PushLocalFrame(320), JNI_OK);
+                let mut top = null_mut();
+                for _ in 0..100 {
+                    top = new_exception(env, "java/lang/RuntimeException", None, top);
+                }
+
+                assert_eq!(env.get_exception_chain(top, usize::MAX).len(), 64);
+                assert_eq!(env.get_exception_chain(top, 80).len(), 64);
+                assert_eq!(env.get_exception_chain(top, 10).len(), 10);
+                assert!(!env.ExceptionCheck());
+                env.PopLocalFrame(null_mut());
+            });
+        }
+    }
+
+    #[test]
+    fn test_cycle() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                //initCause only works if the cause was not set by the constructor.
+                let runtime_exception = env.FindClass("java/lang/RuntimeException");
+                let constructor = env.GetMethodID(runtime_exception, "<init>", "(Ljava/lang/String;)V");
+                let b = env.NewObjectA(runtime_exception, constructor, jtypes!(env.NewStringUTF("b")).as_ptr());
+                let a = new_exception(env, "java/lang/RuntimeException", Some("a"), b);
+                let throwable_class = env.FindClass("java/lang/Throwable");
+                let init_cause = env.GetMethodID(throwable_class, "initCause", "(Ljava/lang/Throwable;)Ljava/lang/Throwable;");
+                let result = env.CallObjectMethodA(b, init_cause, jtypes!(a).as_ptr());
+                assert!(!env.ExceptionCheck());
+                env.DeleteLocalRef(result);
+
+                let chain = env.get_exception_chain(a, 32);
+                assert_eq!(info(&chain), vec![("java.lang.RuntimeException", Some("a")), ("java.lang.RuntimeException", Some("b"))]);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
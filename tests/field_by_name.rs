@@ -0,0 +1,90 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe fn fixture(env: JNIEnv) -> jobject {
+        let base = env.FindClass("FieldLookupFixtureBase");
+        let base = if base.is_null() {
+            env.ExceptionClear();
+            env.DefineClass_from_slice("FieldLookupFixtureBase", null_mut(), include_bytes!("../java_testcode/FieldLookupFixtureBase.class"))
+        } else {
+            base
+        };
+        assert!(!base.is_null());
+        let class = env.FindClass("FieldLookupFixture");
+        let class = if class.is_null() {
+            env.ExceptionClear();
+            env.DefineClass_from_slice("FieldLookupFixture", null_mut(), include_bytes!("../java_testcode/FieldLookupFixture.class"))
+        } else {
+            class
+        };
+        assert!(!class.is_null());
+        let constructor = env.GetMethodID(class, "<init>", "()V");
+        let obj = env.NewObject0(class, constructor);
+        assert!(!env.ExceptionCheck());
+        obj
+    }
+
+    #[test]
+    fn test_read() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let obj = fixture(env);
+                assert_eq!(env.get_int_field_by_name(obj, "count", true), Ok(7));
+                assert_eq!(env.get_long_field_by_name(obj, "big", false), Ok(1 << 40));
+                assert_eq!(env.get_boolean_field_by_name(obj, "flag", true), Ok(true));
+                assert_eq!(env.get_string_field_by_name(obj, "name", true), Ok(Some("fixture \u{20AC}".to_string())));
+                assert_eq!(env.get_string_field_by_name(obj, "missingName", true), Ok(None));
+                assert_eq!(env.get_int_field_by_name(obj, "inherited", true), Ok(11));
+
+                let nothing = env.get_object_field_by_name(obj, "nothing", "Ljava/lang/Object;", true).expect("nothing not found");
+                assert!(nothing.is_null());
+                let something = env.get_object_field_by_name(obj, "something", "Ljava/lang/Object;", true).expect("something not found");
+                assert_eq!(env.GetStringUTFChars_as_string(something).as_deref(), Some("something"));
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let obj = fixture(env);
+                assert_eq!(env.get_int_field_by_name(obj, "missing", true), Err(FieldLookupError::NoSuchField("missing".to_string())));
+                assert!(!env.ExceptionCheck());
+
+                assert_eq!(env.get_long_field_by_name(obj, "count", false), Err(FieldLookupError::NoSuchField("count".to_string())));
+                assert!(!env.ExceptionCheck());
+
+                assert_eq!(
+                    env.get_long_field_by_name(obj, "count", true),
+                    Err(FieldLookupError::WrongType {
+                        field: "count".to_string(),
+                        expected: "long".to_string(),
+                        actual: "int".to_string(),
+                    })
+                );
+                assert!(!env.ExceptionCheck());
+
+                assert_eq!(
+                    env.get_string_field_by_name(obj, "nothing", true),
+                    Err(FieldLookupError::WrongType {
+                        field: "nothing".to_string(),
+                        expected: "java.lang.String".to_string(),
+                        actual: "java.lang.Object".to_string(),
+                    })
+                );
+                assert!(!env.ExceptionCheck());
+
+                //Static fields are not instance fields.
+                assert_eq!(
+                    env.get_int_field_by_name(obj, "staticCount", true),
+                    Err(FieldLookupError::NoSuchField("staticCount".to_string()))
+                );
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
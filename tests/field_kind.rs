@@ -0,0 +1,154 @@
+#[cfg(all(feature = "test-util", feature = "asserts"))]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe fn define(env: JNIEnv, name: &str, bytes: &[u8]) -> jclass {
+        let class = env.FindClass(name);
+        if !class.is_null() {
+            return class;
+        }
+
+        env.ExceptionClear();
+        let class = env.DefineClass_from_slice(name, null_mut(), bytes);
+        assert!(!class.is_null());
+        class
+    }
+
+    unsafe fn classes(env: JNIEnv) -> (jclass, jclass, jclass) {
+        let fixture = define(env, "FieldKindFixture", include_bytes!("../java_testcode/FieldKindFixture.class"));
+        let sub = define(env, "FieldKindFixtureSub", include_bytes!("../java_testcode/FieldKindFixtureSub.class"));
+        let other = define(env, "FieldKindFixtureOther", include_bytes!("../java_testcode/FieldKindFixtureOther.class"));
+        (fixture, sub, other)
+    }
+
+    fn panic_message(f: impl FnOnce()) -> String {
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).expect_err("expected a panic");
+        payload.downcast_ref::<String>().expect("panic payload is not a String").clone()
+    }
+
+    #[test]
+    fn test_valid() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let (fixture, sub, _) = classes(env);
+                let static_count = env.GetStaticFieldID(fixture, "staticCount", "I");
+                let count = env.GetFieldID(fixture, "count", "I");
+                let constructor = env.GetMethodID(sub, "<init>", "()V");
+                let obj = env.NewObject0(sub, constructor);
+
+                assert_eq!(env.GetStaticIntField(fixture, static_count), 3);
+                assert_eq!(env.GetStaticIntField(sub, static_count), 3);
+                assert_eq!(env.GetIntField(obj, count), 7);
+                env.SetStaticIntField(sub, static_count, 3);
+
+                let reflected = env.ToReflectedField(fixture, static_count, true);
+                assert_eq!(env.FromReflectedField(reflected), static_count);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_swapped() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let (fixture, _, _) = classes(env);
+                let static_count = env.GetStaticFieldID(fixture, "staticCount", "I");
+                let count = env.GetFieldID(fixture, "count", "I");
+                let constructor = env.GetMethodID(fixture, "<init>", "()V");
+                let obj = env.NewObject0(fixture, constructor);
+
+                let message = panic_message(|| {
+                    env.GetStaticIntField(fixture, count);
+                });
+                assert_eq!(message, "GetStaticIntField fieldID is an instance field but a static field was expected");
+
+                let message = panic_message(|| env.SetStaticIntField(fixture, count, 1));
+                assert_eq!(message, "SetStaticIntField fieldID is an instance field but a static field was expected");
+
+                let message = panic_message(|| {
+                    env.GetIntField(obj, static_count);
+                });
+                assert_eq!(message, "GetIntField fieldID is a static field but an instance field was expected");
+
+                let message = panic_message(|| env.SetIntField(obj, static_count, 1));
+                assert_eq!(message, "SetIntField fieldID is a static field but an instance field was expected");
+
+                let message = panic_message(|| {
+                    env.ToReflectedField(fixture, static_count, false);
+                });
+                assert_eq!(message, "ToReflectedField fieldID is a static field but an instance field was expected");
+            });
+        }
+    }
+
+    #[test]
+    fn test_reflected() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let (fixture, _, _) = classes(env);
+                let count = env.GetFieldID(fixture, "count", "I");
+                let reflected = env.ToReflectedField(fixture, count, false);
+                let from_reflected = env.FromReflectedField(reflected);
+                assert_eq!(from_reflected, count);
+
+                let message = panic_message(|| {
+                    env.GetStaticIntField(fixture, from_reflected);
+                });
+                assert_eq!(message, "GetStaticIntField fieldID is an instance field but a static field was expected");
+            });
+        }
+    }
+
+    #[test]
+    fn test_declaring_class() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let (fixture, sub, other) = classes(env);
+                let other_field = env.GetStaticFieldID(other, "other", "I");
+                let static_count = env.GetStaticFieldID(fixture, "staticCount", "I");
+
+                let message = panic_message(|| {
+                    env.GetStaticIntField(fixture, other_field);
+                });
+                assert_eq!(
+                    message,
+                    "GetStaticIntField field is declared in FieldKindFixtureOther which is not FieldKindFixture or a superclass of it"
+                );
+
+                let message = panic_message(|| env.SetStaticIntField(sub, other_field, 1));
+                assert_eq!(
+                    message,
+                    "SetStaticIntField field is declared in FieldKindFixtureOther which is not FieldKindFixtureSub or a superclass of it"
+                );
+
+                let message = panic_message(|| {
+                    env.GetStaticIntField(other, static_count);
+                });
+                assert_eq!(
+                    message,
+                    "GetStaticIntField field is declared in FieldKindFixture which is not FieldKindFixtureOther or a superclass of it"
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn test_type() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let (fixture, _, _) = classes(env);
+                let static_count = env.GetStaticFieldID(fixture, "staticCount", "I");
+
+                let message = panic_message(|| {
+                    env.GetStaticLongField(fixture, static_count);
+                });
+                assert_eq!(message, "GetStaticLongField type of field is int but expected long");
+
+                let message = panic_message(|| env.SetStaticObjectField(fixture, static_count, null_mut()));
+                assert_eq!(message, "SetStaticObjectField type of field is int but expected object");
+            });
+        }
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe fn new_url_class_loader(env: JNIEnv, dir: &std::path::Path) -> jobject {
+        let file_class = env.FindClass("java/io/File");
+        let file_constructor = env.GetMethodID(file_class, "<init>", "(Ljava/lang/String;)V");
+        let to_uri = env.GetMethodID(file_class, "toURI", "()Ljava/net/URI;");
+        let uri_class = env.FindClass("java/net/URI");
+        let to_url = env.GetMethodID(uri_class, "toURL", "()Ljava/net/URL;");
+        let url_class = env.FindClass("java/net/URL");
+        let loader_class = env.FindClass("java/net/URLClassLoader");
+        let loader_constructor = env.GetMethodID(loader_class, "<init>", "([Ljava/net/URL;)V");
+
+        let path = env.NewStringUTF(dir.to_str().unwrap());
+        let file = env.NewObject1(file_class, file_constructor, path);
+        let uri = env.CallObjectMethod0(file, to_uri);
+        assert!(!env.ExceptionCheck());
+        let url = env.CallObjectMethod0(uri, to_url);
+        assert!(!env.ExceptionCheck());
+        let urls = env.NewObjectArray(1, url_class, url);
+        let loader = env.NewObject1(loader_class, loader_constructor, urls);
+        assert!(!env.ExceptionCheck());
+        assert!(!loader.is_null());
+
+        for obj in [file_class, uri_class, url_class, loader_class, path, file, uri, url, urls] {
+            env.DeleteLocalRef(obj);
+        }
+
+        loader
+    }
+
+    #[test]
+    fn test() {
+        unsafe {
+            let (_, env) = testing::test_vm();
+            let dir = std::env::temp_dir().join(format!("jni_simple_find_class_loader_{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("RegisterTest.class"), include_bytes!("../java_testcode/RegisterTest.class")).unwrap();
+
+            let loader = new_url_class_loader(env, &dir);
+
+            let class = env.FindClass("RegisterTest");
+            assert!(class.is_null());
+            assert!(env.ExceptionCheck());
+            env.ExceptionClear();
+
+            let class = env.FindClass_with_loader(loader, "RegisterTest");
+            assert!(!env.ExceptionCheck());
+            assert!(!class.is_null());
+            let class_class = env.GetObjectClass(class);
+            let get_class_loader = env.GetMethodID(class_class, "getClassLoader", "()Ljava/lang/ClassLoader;");
+            env.DeleteLocalRef(class_class);
+            let defining_loader = env.CallObjectMethod0(class, get_class_loader);
+            assert!(!env.ExceptionCheck());
+            assert!(env.IsSameObject(loader, defining_loader));
+            env.DeleteLocalRef(defining_loader);
+            env.DeleteLocalRef(class);
+
+            let class = env.FindClass_with_loader(loader, "java/util/ArrayList");
+            let expected = env.FindClass("java/util/ArrayList");
+            assert!(!class.is_null());
+            assert!(env.IsSameObject(class, expected));
+            env.DeleteLocalRef(class);
+            env.DeleteLocalRef(expected);
+
+            //Array classes are not loaded by loadClass.
+            let array_class = env.FindClass_with_loader(loader, "[LRegisterTest;");
+            assert!(!env.ExceptionCheck());
+            assert!(!array_class.is_null());
+            let class_class = env.GetObjectClass(array_class);
+            let get_component_type = env.GetMethodID(class_class, "getComponentType", "()Ljava/lang/Class;");
+            env.DeleteLocalRef(class_class);
+            let component = env.CallObjectMethod0(array_class, get_component_type);
+            assert!(!env.ExceptionCheck());
+            assert_eq!(env.get_class_name(component).as_deref(), Some("RegisterTest"));
+            env.DeleteLocalRef(component);
+            env.DeleteLocalRef(array_class);
+
+            for name in ["[I", "[[Ljava/lang/String;"] {
+                let class = env.FindClass_with_loader(loader, name);
+                assert!(!env.ExceptionCheck());
+                let expected = env.FindClass(name);
+                assert!(env.IsSameObject(class, expected));
+                env.DeleteLocalRef(class);
+                env.DeleteLocalRef(expected);
+            }
+
+            let class = env.FindClass_with_loader(loader, "[LDoesNotExist;");
+            assert!(class.is_null());
+            assert!(env.ExceptionCheck());
+            env.ExceptionClear();
+
+            let class = env.FindClass_with_loader(loader, "DoesNotExist");
+            assert!(class.is_null());
+            let exc = env.ExceptionOccurred();
+            assert!(!exc.is_null());
+            env.ExceptionClear();
+            let class_not_found = env.FindClass("java/lang/ClassNotFoundException");
+            assert!(env.IsInstanceOf(exc, class_not_found));
+            env.DeleteLocalRef(class_not_found);
+            env.DeleteLocalRef(exc);
+
+            let class = env.FindClass_with_loader(null_mut(), "java/lang/String");
+            assert!(!class.is_null());
+            env.DeleteLocalRef(class);
+
+            env.DeleteLocalRef(loader);
+            _ = std::fs::remove_dir_all(&dir);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ffi::c_void;
+
+    const JVMTI_VERSION_1_2: jint = 0x3001_0200;
+
+    #[test]
+    fn test() {
+        unsafe {
+            let (vm, env) = testing::test_vm();
+
+            let jni: JNIEnv = vm.GetEnv_as(JNI_VERSION_1_8).unwrap();
+            assert_eq!(jni.into_raw(), env.into_raw());
+
+            let raw: *mut c_void = vm.GetEnv_as(JNI_VERSION_1_8).unwrap();
+            assert_eq!(raw, env.into_raw());
+
+            let jvmti: *mut c_void = vm.GetEnv_as(JVMTI_VERSION_1_2).unwrap();
+            assert!(!jvmti.is_null());
+            assert_ne!(jvmti, env.into_raw());
+
+            assert_eq!(vm.GetEnv_as::<JNIEnv>(JVMTI_VERSION_1_2).unwrap_err(), JNI_EVERSION);
+            assert_eq!(vm.GetEnv(JVMTI_VERSION_1_2).unwrap_err(), JNI_EVERSION);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-#[cfg(feature = "loadjvm")]
+#[cfg(feature = "test-util")]
 pub mod test {
     use jni_simple::*;
     use std::ptr::null_mut;
@@ -6,13 +6,10 @@ pub mod test {
     #[test]
     fn test() {
         unsafe {
-            load_jvm_from_java_home().expect("failed to load jvm");
-            let args: Vec<String> = vec![];
-            let (vm, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &args).expect("failed to create java VM");
-            let rt = env.GetObjectRefType(null_mut());
-            assert_eq!(jobjectRefType::JNIInvalidRefType, rt);
-
-            vm.DestroyJavaVM();
+            testing::with_test_frame(|env| {
+                let rt = env.GetObjectRefType(null_mut());
+                assert_eq!(jobjectRefType::JNIInvalidRefType, rt);
+            });
         }
     }
 }
@@ -0,0 +1,139 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    const TARGET: &[u8] = include_bytes!("../java_testcode/HiddenClassTarget.class");
+
+    unsafe extern "system" fn define_native(env: JNIEnv, host_class: jclass) -> jclass {
+        match env.define_hidden_class(host_class, TARGET, true) {
+            Ok(class) => class,
+            Err(throwable) => {
+                env.Throw(throwable);
+                null_mut()
+            }
+        }
+    }
+
+    unsafe fn assert_hidden_target(env: JNIEnv, class: jclass) {
+        assert!(!class.is_null());
+        let name = env.get_class_name(class).expect("get_class_name failed");
+        assert!(name.starts_with("HiddenClassTarget/"), "{name}");
+
+        let class_class = env.GetObjectClass(class);
+        let is_hidden = env.GetMethodID(class_class, "isHidden", "()Z");
+        assert!(env.CallBooleanMethod0(class, is_hidden));
+        assert!(!env.ExceptionCheck());
+
+        let answer = env.GetStaticMethodID(class, "answer", "()I");
+        assert_eq!(env.CallStaticIntMethod0(class, answer), 42);
+        assert!(!env.ExceptionCheck());
+    }
+
+    /// Hidden classes require Java 15, older JVMs lack `MethodHandles.Lookup#defineHiddenClass`.
+    unsafe fn supports_hidden_classes(env: JNIEnv) -> bool {
+        let lookup_class = env.FindClass("java/lang/invoke/MethodHandles$Lookup");
+        assert!(!lookup_class.is_null());
+        let define_hidden_class = env.GetMethodID(
+            lookup_class,
+            "defineHiddenClass",
+            "([BZ[Ljava/lang/invoke/MethodHandles$Lookup$ClassOption;)Ljava/lang/invoke/MethodHandles$Lookup;",
+        );
+        env.DeleteLocalRef(lookup_class);
+        if define_hidden_class.is_null() {
+            env.ExceptionClear();
+            return false;
+        }
+        true
+    }
+
+    #[test]
+    fn test() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                if !supports_hidden_classes(env) {
+                    eprintln!("skipping hidden class test, the JVM does not support hidden classes");
+                    return;
+                }
+
+                let host = env.DefineClass_from_slice("HiddenClassHost", null_mut(), include_bytes!("../java_testcode/HiddenClassHost.class"));
+                assert!(!host.is_null());
+
+                //Attached thread without java frames, MethodHandles.lookup() has no caller.
+                let throwable = env
+                    .define_hidden_class(host, TARGET, true)
+                    .expect_err("define_hidden_class without a java caller succeeded");
+                assert!(!env.ExceptionCheck());
+                assert!(!throwable.is_null());
+                env.DeleteLocalRef(throwable);
+
+                //Lookup provided by java.
+                let lookup_method = env.GetStaticMethodID(host, "lookup", "()Ljava/lang/invoke/MethodHandles$Lookup;");
+                let lookup = env.CallStaticObjectMethod0(host, lookup_method);
+                assert!(!env.ExceptionCheck());
+                let class = env.define_hidden_class_with_lookup(lookup, TARGET, true).expect("define_hidden_class_with_lookup failed");
+                assert_hidden_target(env, class);
+
+                //Invalid class file.
+                let throwable = env
+                    .define_hidden_class_with_lookup(lookup, &TARGET[..TARGET.len() / 2], true)
+                    .expect_err("truncated class file was accepted");
+                assert!(!env.ExceptionCheck());
+                let class_format_error = env.FindClass("java/lang/ClassFormatError");
+                assert!(env.IsInstanceOf(throwable, class_format_error));
+
+                //Native method, MethodHandles.lookup() is called on behalf of the host class.
+                let method = JNINativeMethod::new(c"defineNative".as_ptr(), c"()Ljava/lang/Class;".as_ptr(), define_native as *const std::ffi::c_void);
+                assert_eq!(env.RegisterNatives_from_slice(host, &[method]), JNI_OK);
+                let call_define_native = env.GetStaticMethodID(host, "callDefineNative", "()Ljava/lang/Class;");
+                let class = env.CallStaticObjectMethod0(host, call_define_native);
+                if env.ExceptionCheck() {
+                    env.ExceptionDescribe();
+                    panic!("define_hidden_class in a native method failed");
+                }
+                assert_hidden_target(env, class);
+            });
+        }
+    }
+
+    #[test]
+    fn test_unsupported() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                if supports_hidden_classes(env) {
+                    return;
+                }
+
+                let host = env.DefineClass_from_slice("HiddenClassHost", null_mut(), include_bytes!("../java_testcode/HiddenClassHost.class"));
+                assert!(!host.is_null());
+                let lookup_method = env.GetStaticMethodID(host, "lookup", "()Ljava/lang/invoke/MethodHandles$Lookup;");
+                let lookup = env.CallStaticObjectMethod0(host, lookup_method);
+                assert!(!env.ExceptionCheck());
+
+                let throwable = env
+                    .define_hidden_class_with_lookup(lookup, TARGET, true)
+                    .expect_err("define_hidden_class_with_lookup succeeded without hidden class support");
+                assert!(!env.ExceptionCheck());
+                let no_class_def_found_error = env.FindClass("java/lang/NoClassDefFoundError");
+                assert!(env.IsInstanceOf(throwable, no_class_def_found_error));
+
+                //MethodHandles.privateLookupIn was added in Java 9.
+                let method_handles = env.FindClass("java/lang/invoke/MethodHandles");
+                let private_lookup_in = env.GetStaticMethodID(
+                    method_handles,
+                    "privateLookupIn",
+                    "(Ljava/lang/Class;Ljava/lang/invoke/MethodHandles$Lookup;)Ljava/lang/invoke/MethodHandles$Lookup;",
+                );
+                if private_lookup_in.is_null() {
+                    env.ExceptionClear();
+                    let throwable = env
+                        .define_hidden_class(host, TARGET, true)
+                        .expect_err("define_hidden_class succeeded without hidden class support");
+                    assert!(!env.ExceptionCheck());
+                    let no_such_method_error = env.FindClass("java/lang/NoSuchMethodError");
+                    assert!(env.IsInstanceOf(throwable, no_such_method_error));
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(feature = "loadjvm")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test() {
+        unsafe {
+            assert!(vm_creation_options().is_none());
+
+            load_jvm_from_java_home().expect("failed to load jvm");
+            let args: Vec<String> = vec!["-Djni.simple.input=arguments".to_string(), "-Xmx64m".to_string()];
+            let (_vm, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &args).expect("failed to create java VM");
+
+            assert_eq!(vm_creation_options(), Some(args.as_slice()));
+
+            let reported = env.get_input_arguments().expect("get_input_arguments failed");
+            assert!(!env.ExceptionCheck());
+            assert!(reported.contains(&"-Djni.simple.input=arguments".to_string()), "{reported:?}");
+            assert!(reported.contains(&"-Xmx64m".to_string()), "{reported:?}");
+        }
+    }
+}
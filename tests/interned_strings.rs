@@ -0,0 +1,38 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test() {
+        unsafe {
+            let (_, env) = testing::test_vm();
+            let interned = InternedStrings::new();
+            assert!(interned.is_empty());
+
+            let hello = interned.get(&env, "Hello World");
+            assert!(!hello.is_null());
+            assert_eq!(env.GetObjectRefType(hello), jobjectRefType::JNIGlobalRefType);
+            assert_eq!(env.GetStringUTFChars_as_string(hello).unwrap().as_str(), "Hello World");
+            assert_eq!(interned.get(&env, "Hello World"), hello);
+            assert_eq!(interned.len(), 1);
+
+            let local = env.NewStringUTF("Hello World");
+            let string_class = env.GetObjectClass(local);
+            let intern = env.GetMethodID(string_class, "intern", "()Ljava/lang/String;");
+            let java_interned = env.CallObjectMethod0(local, intern);
+            assert!(!env.ExceptionCheck());
+            assert!(env.IsSameObject(hello, java_interned));
+            env.DeleteLocalRef(java_interned);
+            env.DeleteLocalRef(string_class);
+            env.DeleteLocalRef(local);
+
+            let special = interned.get(&env, "nul\0 and \u{1F600}");
+            assert!(!special.is_null());
+            assert_eq!(env.GetStringLength(special), 11);
+            assert_eq!(interned.len(), 2);
+
+            interned.clear(&env);
+            assert!(interned.is_empty());
+        }
+    }
+}
@@ -0,0 +1,47 @@
+//The asserts feature checks for exceptions before every call, which hides the provoked warning from -Xcheck:jni.
+#[cfg(all(feature = "test-util", not(feature = "asserts"), unix))]
+pub mod test {
+    use jni_simple::testing::JniCheckCollector;
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    //This is the only test in this file, so no other test can add warnings to the process global collector.
+    #[test]
+    fn test_collect_warnings() {
+        let collector = JniCheckCollector::global();
+        unsafe {
+            //with_test_frame would fail on the provoked warning.
+            let (_, env) = testing::test_vm();
+            assert!(collector.is_installed());
+            collector.assert_no_warnings(&[]);
+
+            assert_eq!(env.PushLocalFrame(16), JNI_OK);
+            //Only the start of the line is printed through the JVM, the warning must still be collected after it.
+            assert_eq!(env.ThrowNew(env.FindClass("java/lang/IllegalStateException"), "described"), JNI_OK);
+            env.ExceptionDescribe();
+            let sys = env.FindClass("java/lang/System");
+            let get_prop = env.GetStaticMethodID(sys, "getProperty", "(Ljava/lang/String;)Ljava/lang/String;");
+            let key = env.NewStringUTF("java.version");
+            let first = env.CallStaticObjectMethod1(sys, get_prop, key);
+            //Calling the method again without checking for an exception first makes -Xcheck:jni print a warning.
+            let second = env.CallStaticObjectMethod1(sys, get_prop, key);
+            assert!(!env.ExceptionCheck());
+            assert!(!first.is_null());
+            assert!(!second.is_null());
+            env.PopLocalFrame(null_mut());
+        }
+
+        let expected = "WARNING in native method: JNI call made without checking exceptions when required to from CallStaticObjectMethod";
+        assert!(collector.warnings().iter().any(|warning| warning == expected), "{:?}", collector.warnings());
+        collector.assert_no_warnings(&["without checking exceptions"]);
+
+        let message = std::panic::catch_unwind(|| collector.assert_no_warnings(&["something else"])).expect_err("expected a panic");
+        let message = message.downcast_ref::<String>().expect("panic payload is not a String");
+        assert!(message.starts_with("-Xcheck:jni reported "), "{message}");
+        assert!(message.contains(expected), "{message}");
+
+        collector.clear();
+        assert!(collector.warnings().is_empty());
+        collector.assert_no_warnings(&[]);
+    }
+}
@@ -0,0 +1,54 @@
+use jni_simple::*;
+
+#[test]
+fn test_from_code() {
+    assert_eq!(JniError::from_code(JNI_OK), Ok(()));
+    let known = [
+        (JNI_ERR, JniError::Err, "JNI_ERR"),
+        (JNI_EDETACHED, JniError::Detached, "JNI_EDETACHED"),
+        (JNI_EVERSION, JniError::Version, "JNI_EVERSION"),
+        (JNI_ENOMEM, JniError::NoMemory, "JNI_ENOMEM"),
+        (JNI_EEXIST, JniError::Exist, "JNI_EEXIST"),
+        (JNI_EINVAL, JniError::Inval, "JNI_EINVAL"),
+    ];
+    for (code, error, name) in known {
+        assert_eq!(JniError::from_code(code), Err(error));
+        assert_eq!(error.code(), code);
+        assert_eq!(jint::from(error), code);
+        assert_eq!(error.to_string(), name);
+    }
+
+    for code in [-7, -1000, jint::MIN, 1] {
+        let error = JniError::from_code(code).expect_err("not JNI_OK");
+        assert_eq!(error, JniError::Unknown(code));
+        assert_eq!(error.code(), code);
+        assert_eq!(error.to_string(), format!("unknown JNI error code {code}"));
+    }
+}
+
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test_result_variants() {
+        unsafe {
+            let (vm, env) = testing::test_vm();
+            assert_eq!(env.EnsureLocalCapacity_result(16), Ok(()));
+            assert_eq!(env.PushLocalFrame_result(16), Ok(()));
+            env.PopLocalFrame(std::ptr::null_mut());
+
+            assert_eq!(vm.GetEnv_result(JNI_VERSION_1_8).map(|found| found == env), Ok(true));
+            assert_eq!(vm.GetEnv_result(0x7fff_0000).map(|_| ()), Err(JniError::Version));
+
+            let detached = std::thread::spawn(move || {
+                let result = vm.GetEnv_result(JNI_VERSION_1_8).map(|_| ());
+                let attached = vm.AttachCurrentThread_str_result(JNI_VERSION_1_8, Some("jni_error"), std::ptr::null_mut()).is_ok();
+                (result, attached, vm.DetachCurrentThread_result())
+            })
+            .join()
+            .expect("thread panicked");
+            assert_eq!(detached, (Err(JniError::Detached), true, Ok(())));
+        }
+    }
+}
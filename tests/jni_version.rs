@@ -0,0 +1,35 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test_attached() {
+        unsafe {
+            let (vm, env) = testing::test_vm();
+            let version = max_supported_jni_version(vm);
+            assert!(version >= JNI_VERSION_1_8, "{version:x}");
+            assert!(version <= env.GetVersion(), "{version:x}");
+            assert!(vm.GetEnv(version).is_ok());
+
+            assert_eq!(desired_or_max(JNI_VERSION_1_8, vm), JNI_VERSION_1_8);
+            assert_eq!(desired_or_max(JNI_VERSION_24 + 0x0001_0000, vm), version);
+        }
+    }
+
+    #[test]
+    fn test_detached() {
+        unsafe {
+            let (vm, env) = testing::test_vm();
+            let expected = max_supported_jni_version(vm);
+            assert!(expected <= env.GetVersion());
+            let vm_ptr = vm;
+            std::thread::spawn(move || {
+                assert_eq!(vm_ptr.GetEnv(JNI_VERSION_1_8).err(), Some(JNI_EDETACHED));
+                assert_eq!(max_supported_jni_version(vm_ptr), expected);
+                assert_eq!(vm_ptr.GetEnv(JNI_VERSION_1_8).err(), Some(JNI_EDETACHED));
+            })
+            .join()
+            .expect("thread panicked");
+        }
+    }
+}
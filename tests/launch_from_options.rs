@@ -0,0 +1,37 @@
+#[cfg(feature = "loadjvm")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::addr_of_mut;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test() {
+        unsafe {
+            load_jvm_from_java_home().expect("failed to load jvm");
+
+            let prop = c"-Drusttest=from_options".to_owned();
+            let unknown = c"-XX:+ThisOptionDoesNotExist".to_owned();
+            let mut options = [
+                JavaVMOption::new(prop.as_ptr().cast_mut(), null_mut()),
+                JavaVMOption::new(unknown.as_ptr().cast_mut(), null_mut()),
+            ];
+            let mut args = JavaVMInitArgs::from_options(JNI_VERSION_1_8, &mut options, true);
+            assert_eq!(args.nOptions(), 2);
+            assert_eq!(args.options(), options.as_mut_ptr());
+            assert_eq!(args.ignoreUnrecognized(), 1);
+
+            let (_, env) = JNI_CreateJavaVM(addr_of_mut!(args)).expect("failed to create jvm");
+
+            let sys = env.FindClass("java/lang/System");
+            let get_prop = env.GetStaticMethodID(sys, "getProperty", "(Ljava/lang/String;)Ljava/lang/String;");
+
+            let str = env.NewStringUTF("rusttest");
+            let obj = env.CallStaticObjectMethodA(sys, get_prop, [str.into()].as_ptr());
+            assert!(!obj.is_null());
+            let uw = env.GetStringUTFChars_as_string(obj).unwrap();
+            assert_eq!("from_options", uw.as_str());
+            env.DeleteLocalRef(obj);
+            env.DeleteLocalRef(str);
+        }
+    }
+}
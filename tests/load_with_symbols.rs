@@ -0,0 +1,33 @@
+#[cfg(feature = "loadjvm")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test() {
+        unsafe {
+            let path = find_libjvm_in_java_home().expect("libjvm not found in JAVA_HOME");
+
+            let err = load_jvm_from_library_with_symbols(&path, "JNI_CreateJavaVM_missing", "JNI_GetCreatedJavaVMs", None).expect_err("symbol should not exist");
+            assert!(err.contains("JNI_CreateJavaVM_missing not found"), "{err}");
+            let err = load_jvm_from_library_with_symbols(&path, "JNI_CreateJavaVM", "JNI_GetCreatedJavaVMs", Some("JNI_GetDefaultJavaVMInitArgs_missing"))
+                .expect_err("symbol should not exist");
+            assert!(err.contains("JNI_GetDefaultJavaVMInitArgs_missing not found"), "{err}");
+            assert!(!is_jvm_loaded());
+
+            load_jvm_from_library_with_symbols(&path, "JNI_CreateJavaVM", "JNI_GetCreatedJavaVMs", Some("JNI_GetDefaultJavaVMInitArgs")).expect("failed to load jvm");
+            assert!(is_jvm_loaded());
+            assert_eq!(
+                load_jvm_from_library_with_symbols(&path, "JNI_CreateJavaVM", "JNI_GetCreatedJavaVMs", None),
+                Err("JVM already loaded".to_string())
+            );
+
+            let args = JNI_GetDefaultJavaVMInitArgs_version(JNI_VERSION_1_8).expect("JNI_VERSION_1_8 not supported");
+            assert_eq!(args.version(), JNI_VERSION_1_8);
+            assert!(JNI_GetDefaultJavaVMInitArgs_version(0x7FFF_0000).is_err());
+
+            let (vm, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &Vec::new()).expect("failed to create jvm");
+            assert!(env.GetVersion() >= JNI_VERSION_1_8);
+            vm.DestroyJavaVM();
+        }
+    }
+}
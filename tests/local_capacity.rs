@@ -0,0 +1,93 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test_within_capacity() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                assert_eq!(env.ensure_local_capacity_tracked(4), JNI_OK);
+                let result = env.local_capacity_scope(2, |env| {
+                    for _ in 0..10 {
+                        let a = env.NewStringUTF("a");
+                        let b = env.NewStringUTF("b");
+                        env.DeleteLocalRef(a);
+                        env.DeleteLocalRef(b);
+                    }
+                    env.GetStringLength(env.NewStringUTF("abc"))
+                });
+                assert_eq!(result, Ok(3));
+
+                for _ in 0..4 {
+                    let obj = env.NewStringUTF("outer");
+                    assert!(!obj.is_null());
+                }
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[cfg(feature = "asserts")]
+    fn panic_message(f: impl FnOnce()) -> String {
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).expect_err("expected a panic");
+        payload.downcast_ref::<String>().expect("panic payload is not a String").clone()
+    }
+
+    #[cfg(feature = "asserts")]
+    #[test]
+    fn test_exceeded() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let message = panic_message(|| {
+                    _ = env.local_capacity_scope(2, |env| {
+                        env.NewStringUTF("a");
+                        env.NewStringUTF("b");
+                        env.NewStringUTF("c");
+                    });
+                });
+                assert_eq!(
+                    message,
+                    "NewStringUTF created local reference number 3 but only 2 were ensured by ensure_local_capacity_tracked or PushLocalFrame"
+                );
+
+                //Nested frames have their own budget.
+                _ = env.local_capacity_scope(1, |env| {
+                    assert_eq!(env.PushLocalFrame(8), JNI_OK);
+                    for _ in 0..8 {
+                        env.NewStringUTF("inner");
+                    }
+                    env.PopLocalFrame(std::ptr::null_mut());
+                    env.NewStringUTF("outer");
+                });
+
+                //The result of PopLocalFrame counts in the parent frame.
+                let message = panic_message(|| {
+                    _ = env.local_capacity_scope(1, |env| {
+                        assert_eq!(env.PushLocalFrame(1), JNI_OK);
+                        let result = env.PopLocalFrame(env.NewStringUTF("x"));
+                        assert!(!result.is_null());
+                        env.FindClass("java/lang/String");
+                    });
+                });
+                assert_eq!(
+                    message,
+                    "FindClass created local reference number 2 but only 1 were ensured by ensure_local_capacity_tracked or PushLocalFrame"
+                );
+
+                //Raising the capacity.
+                _ = env.local_capacity_scope(1, |env| {
+                    env.NewStringUTF("a");
+                    assert_eq!(env.ensure_local_capacity_tracked(2), JNI_OK);
+                    env.NewStringUTF("b");
+                    env.NewStringUTF("c");
+                });
+
+                //Tracking ended with the scope.
+                for _ in 0..20 {
+                    env.NewStringUTF("untracked");
+                }
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
@@ -12,6 +12,36 @@ fn test() {
         assert_eq!(n[3].double(), 4f64);
         assert_eq!(n[4].object(), m);
 
-        assert_ne!(0usize, std::hint::black_box(jtypes!(1i32, 2i32, 3i32, 4f64, m)).as_ptr() as usize)
+        let args = std::hint::black_box(jtypes!(1i32, 2i32, 3i32, 4f64, m));
+        assert_ne!(0usize, args.as_ptr() as usize)
+    }
+}
+
+#[test]
+fn test_tagged() {
+    unsafe {
+        let mut v = vec![64; 0];
+        let m: jobject = v.as_mut_ptr().cast();
+        let n = jtypes!(long: 12, int: 5, null, object: m, 7i16, double: 1.5, float: 2.5, boolean: true, byte: -3, char: 65, short: 9,);
+        assert_eq!(n.len(), 11);
+        assert_eq!(n[0].long(), 12);
+        assert_eq!(n[1].int(), 5);
+        assert!(n[2].object().is_null());
+        assert_eq!(n[3].object(), m);
+        assert_eq!(n[4].short(), 7);
+        assert_eq!(n[5].double(), 1.5);
+        assert_eq!(n[6].float(), 2.5);
+        assert!(n[7].boolean());
+        assert_eq!(n[8].byte(), -3);
+        assert_eq!(n[9].char(), 65);
+        assert_eq!(n[10].short(), 9);
+
+        const ARGS: [jtype; 3] = jtypes!(long: i64::MAX, int: -1, null);
+        assert_eq!(ARGS[0].long(), i64::MAX);
+        assert_eq!(ARGS[1].int(), -1);
+        assert!(ARGS[2].object().is_null());
+
+        let empty: [jtype; 0] = jtypes!();
+        assert!(empty.is_empty());
     }
 }
@@ -0,0 +1,58 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_double_round_trip() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let rows: [&[f64]; 4] = [&[1.0, 2.5, f64::NAN], &[], &[-0.0, f64::INFINITY], &[42.0]];
+                let matrix = env.new_double_matrix(&rows);
+                assert!(!matrix.is_null());
+                assert_eq!(env.GetArrayLength(matrix), 4);
+
+                //null rows are returned as empty rows.
+                env.SetObjectArrayElement(matrix, 3, null_mut());
+                let copy = env.get_double_matrix(matrix).expect("exception thrown");
+                assert!(!env.ExceptionCheck());
+                assert_eq!(copy.len(), 4);
+                assert_eq!(copy[0].len(), 3);
+                assert_eq!(copy[0][..2], [1.0, 2.5]);
+                assert!(copy[0][2].is_nan());
+                assert!(copy[1].is_empty());
+                assert_eq!(copy[2], [-0.0, f64::INFINITY]);
+                assert!(copy[2][0].is_sign_negative());
+                assert!(copy[3].is_empty());
+
+                let class = env.GetObjectClass(matrix);
+                assert_eq!(env.get_class_name(class).as_deref(), Some("[[D"));
+            });
+        }
+    }
+
+    #[test]
+    fn test_other_types() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let ints: [&[jint]; 3] = [&[1, 2, 3], &[jint::MIN], &[]];
+                let matrix = env.new_int_matrix(&ints);
+                assert_eq!(env.get_int_matrix(matrix), Some(vec![vec![1, 2, 3], vec![jint::MIN], vec![]]));
+
+                let longs: [&[jlong]; 2] = [&[jlong::MAX, 0], &[7]];
+                let matrix = env.new_long_matrix(&longs);
+                assert_eq!(env.get_long_matrix(matrix), Some(vec![vec![jlong::MAX, 0], vec![7]]));
+
+                let floats: [&[jfloat]; 1] = [&[0.5, -1.25]];
+                let matrix = env.new_float_matrix(&floats);
+                let class = env.GetObjectClass(matrix);
+                assert_eq!(env.get_class_name(class).as_deref(), Some("[[F"));
+                assert_eq!(env.get_float_matrix(matrix), Some(vec![vec![0.5, -1.25]]));
+
+                let empty = env.new_int_matrix(&[]);
+                assert_eq!(env.get_int_matrix(empty), Some(vec![]));
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
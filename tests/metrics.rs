@@ -0,0 +1,33 @@
+#[cfg(all(feature = "test-util", feature = "metrics"))]
+pub mod test {
+    use jni_simple::*;
+
+    /// Returns the amount of calls and the accumulated time of a function from a snapshot.
+    fn recorded(name: &str) -> Option<(u64, u64)> {
+        metrics::snapshot().iter().find(|(n, _, _)| *n == name).map(|(_, count, total_ns)| (*count, *total_ns))
+    }
+
+    //Single test, because reset affects every other test that runs concurrently in this binary.
+    #[test]
+    fn test_metrics() {
+        unsafe {
+            let (vm, env) = testing::test_vm();
+            metrics::reset();
+            assert!(metrics::snapshot().is_empty());
+
+            for _ in 0..1000 {
+                assert!(env.GetVersion() >= JNI_VERSION_1_8);
+            }
+            let (count, total_ns) = recorded("GetVersion").expect("GetVersion was not recorded");
+            assert_eq!(count, 1000);
+            assert!(total_ns > 0);
+
+            vm.GetEnv(JNI_VERSION_1_8).expect("thread is attached");
+            assert_eq!(recorded("GetEnv").map(|(count, _)| count), Some(1));
+            assert!(metrics::snapshot().iter().all(|(_, count, _)| *count > 0));
+
+            metrics::reset();
+            assert!(recorded("GetVersion").is_none());
+        }
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ffi::CStr;
+    use std::ptr::null_mut;
+
+    const STRINGS: [&str; 5] = ["", "java/lang/String", "\u{E4}\u{20AC}\u{FFFF}", "nul\0 and \u{1F600}", "\0\u{10000}\u{10FFFF}\0"];
+
+    #[test]
+    fn test_matches_jvm() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                for s in STRINGS {
+                    let chars = s.encode_utf16().collect::<Vec<u16>>();
+                    let string = env.NewString(chars.as_ptr(), jsize::try_from(chars.len()).unwrap());
+                    let utf = env.GetStringUTFChars(string, null_mut());
+                    assert!(!utf.is_null());
+                    let jvm_bytes = CStr::from_ptr(utf).to_bytes().to_vec();
+                    env.ReleaseStringUTFChars(string, utf);
+                    assert_eq!(jvm_bytes.as_slice(), mutf8::encode(s).as_ref(), "{s:?}");
+                    assert_eq!(mutf8::decode(&jvm_bytes).as_deref(), Ok(s));
+
+                    let mut encoded = mutf8::encode(s).into_owned();
+                    encoded.push(0);
+                    let from_encoded = env.NewStringUTF(encoded.as_ptr());
+                    assert_eq!(env.GetStringUTFLength(from_encoded), jsize::try_from(encoded.len() - 1).unwrap());
+                    let mut round_trip = vec![0u16; usize::try_from(env.GetStringLength(from_encoded)).unwrap()];
+                    env.GetStringRegion_into_slice(from_encoded, 0, &mut round_trip);
+                    assert_eq!(round_trip, chars, "{s:?}");
+
+                    env.DeleteLocalRef(from_encoded);
+                    env.DeleteLocalRef(string);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe fn add(_env: JNIEnv, _class: jclass, a: jint, b: jint) -> jint {
+        a + b
+    }
+
+    unsafe fn greet(env: JNIEnv, this: jobject, name: jstring) -> jstring {
+        let class = env.GetObjectClass(this);
+        let field = env.GetFieldID(class, "prefix", "Ljava/lang/String;");
+        env.DeleteLocalRef(class);
+        let prefix = env.GetObjectField(this, field);
+        let prefix_str = env.GetStringUTFChars_as_string(prefix).expect("prefix is null");
+        env.DeleteLocalRef(prefix);
+        let name = env.GetStringUTFChars_as_string(name).expect("name is null");
+        env.NewStringUTF(format!("{prefix_str} {name}"))
+    }
+
+    unsafe fn scale(_env: JNIEnv, _this: jobject, value: jlong, factor: jfloat) -> jdouble {
+        value as jdouble * jdouble::from(factor)
+    }
+
+    unsafe fn boom(env: JNIEnv, _class: jclass, message: jstring) {
+        let message = env.GetStringUTFChars_as_string(message).expect("message is null");
+        panic!("{message}");
+    }
+
+    #[test]
+    fn test() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = env.DefineClass_from_slice("NativeMethodTest", null_mut(), include_bytes!("../java_testcode/NativeMethodTest.class"));
+                assert!(!class.is_null());
+
+                let methods = [
+                    native_method!("add", "(II)I", fn add(env: JNIEnv, class: jclass, a: jint, b: jint) -> jint),
+                    native_method!("greet", "(Ljava/lang/String;)Ljava/lang/String;", fn greet(env: JNIEnv, this: jobject, name: jstring) -> jstring),
+                    native_method!("scale", "(JF)D", catch_unwind fn scale(env: JNIEnv, this: jobject, value: jlong, factor: jfloat) -> jdouble),
+                    native_method!("boom", "(Ljava/lang/String;)V", catch_unwind fn boom(env: JNIEnv, class: jclass, message: jstring)),
+                ];
+                assert_eq!(env.RegisterNatives_from_slice(class, &methods), JNI_OK);
+
+                let call_add = env.GetStaticMethodID(class, "callAdd", "(II)I");
+                assert_eq!(env.CallStaticIntMethod2(class, call_add, 40, 2), 42);
+                assert!(!env.ExceptionCheck());
+
+                let constructor = env.GetMethodID(class, "<init>", "(Ljava/lang/String;)V");
+                let instance = env.NewObject1(class, constructor, env.NewStringUTF("Hello"));
+                assert!(!instance.is_null());
+                let call_greet = env.GetMethodID(class, "callGreet", "(Ljava/lang/String;)Ljava/lang/String;");
+                let greeting = env.CallObjectMethod1(instance, call_greet, env.NewStringUTF("World"));
+                assert!(!env.ExceptionCheck());
+                assert_eq!(env.GetStringUTFChars_as_string(greeting).as_deref(), Some("Hello World"));
+
+                let scale_method = env.GetMethodID(class, "scale", "(JF)D");
+                assert_eq!(env.CallDoubleMethodA(instance, scale_method, jtypes!(long: 8, float: 0.25).as_ptr()), 2.0);
+                assert!(!env.ExceptionCheck());
+
+                let call_boom = env.GetStaticMethodID(class, "callBoom", "(Ljava/lang/String;)Ljava/lang/String;");
+                let message = env.CallStaticObjectMethod1(class, call_boom, env.NewStringUTF("native panic"));
+                assert!(!env.ExceptionCheck());
+                assert_eq!(env.GetStringUTFChars_as_string(message).as_deref(), Some("native panic"));
+
+                assert_eq!(env.UnregisterNatives(class), JNI_OK);
+            });
+        }
+    }
+
+    #[test]
+    fn test_signature_parameter_count() {
+        assert_eq!(signature_parameter_count("()V"), 0);
+        assert_eq!(signature_parameter_count("(ZBSCIJFD)V"), 8);
+        assert_eq!(signature_parameter_count("(Ljava/lang/String;[I[[Ljava/lang/Object;)[J"), 3);
+        assert_eq!(signature_parameter_count("([Z)Ljava/lang/String;"), 1);
+
+        for invalid in ["", "V", "(I", "(I)", "(X)V", "(Ljava/lang/String)V", "([)V", "(I)VV", "(I)X"] {
+            assert!(std::panic::catch_unwind(|| signature_parameter_count(invalid)).is_err(), "{invalid}");
+        }
+    }
+}
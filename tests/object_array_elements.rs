@@ -0,0 +1,71 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_set_elements() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let object_class = env.FindClass("java/lang/Object");
+                let array = env.NewObjectArray(4, object_class, null_mut());
+                let a = env.NewStringUTF("a");
+                let b = env.NewStringUTF("b");
+
+                env.SetObjectArrayElements(array, 1, &[a, null_mut(), b]).unwrap();
+                assert!(env.GetObjectArrayElement(array, 0).is_null());
+                assert!(env.IsSameObject(env.GetObjectArrayElement(array, 1), a));
+                assert!(env.GetObjectArrayElement(array, 2).is_null());
+                assert!(env.IsSameObject(env.GetObjectArrayElement(array, 3), b));
+
+                let err = env.SetObjectArrayElements(array, 3, &[a, b]).unwrap_err();
+                assert_eq!(err.index, 4);
+                assert!(!env.ExceptionCheck());
+                let index_out_of_bounds = env.FindClass("java/lang/ArrayIndexOutOfBoundsException");
+                assert!(env.IsInstanceOf(err.throwable, index_out_of_bounds));
+                assert!(env.IsSameObject(env.GetObjectArrayElement(array, 3), a));
+
+                env.fill_object_array(array, b).unwrap();
+                for index in 0..4 {
+                    assert!(env.IsSameObject(env.GetObjectArrayElement(array, index), b));
+                }
+                env.fill_object_array(array, null_mut()).unwrap();
+                for index in 0..4 {
+                    assert!(env.GetObjectArrayElement(array, index).is_null());
+                }
+            });
+        }
+    }
+
+    #[cfg(not(feature = "asserts"))]
+    #[test]
+    fn test_array_store_exception() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string_class = env.FindClass("java/lang/String");
+                let array = env.NewObjectArray(2, string_class, null_mut());
+                let a = env.NewStringUTF("a");
+
+                let err = env.SetObjectArrayElements(array, 0, &[a, string_class]).unwrap_err();
+                assert_eq!(err.index, 1);
+                assert!(!env.ExceptionCheck());
+                let array_store = env.FindClass("java/lang/ArrayStoreException");
+                assert!(env.IsInstanceOf(err.throwable, array_store));
+                assert!(env.IsSameObject(env.GetObjectArrayElement(array, 0), a));
+            });
+        }
+    }
+
+    #[cfg(feature = "asserts")]
+    #[test]
+    fn test_array_store_asserts() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string_class = env.FindClass("java/lang/String");
+                let array = env.NewObjectArray(2, string_class, null_mut());
+                let result = std::panic::catch_unwind(|| env.fill_object_array(array, string_class));
+                assert!(result.is_err());
+            });
+        }
+    }
+}
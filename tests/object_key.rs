@@ -0,0 +1,129 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::collections::HashMap;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_same_object() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let object = env.NewStringUTF("key");
+                let other_ref = env.NewLocalRef(object);
+                assert_ne!(object, other_ref);
+
+                let a = env.object_key(object).expect("object_key failed");
+                let b = env.object_key(other_ref).expect("object_key failed");
+                let weak = env.weak_object_key(object).expect("weak_object_key failed");
+                assert!(!a.is_weak());
+                assert!(weak.is_weak());
+                assert_eq!(a.identity_hash(), b.identity_hash());
+                assert!(a.same_object(&env, &b));
+                assert!(a.same_object(&env, &weak));
+                assert_eq!(a, b);
+                assert_eq!(a, weak);
+
+                let mut map = HashMap::new();
+                map.insert(a, 1);
+                assert_eq!(map.get(&b), Some(&1));
+                assert_eq!(map.get(&weak), Some(&1));
+
+                for (key, _) in map {
+                    key.release(&env);
+                }
+                b.release(&env);
+                weak.release(&env);
+                assert!(env.object_key(null_mut()).is_none());
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_distinct_objects() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let mut map = HashMap::new();
+                let mut keys = Vec::new();
+                for i in 0..256 {
+                    let object = env.NewStringUTF(format!("object {i}"));
+                    let key = env.object_key(object).expect("object_key failed");
+                    env.DeleteLocalRef(object);
+                    keys.push(env.object_key(key.reference()).expect("object_key failed"));
+                    assert!(map.insert(key, i).is_none(), "distinct objects must not be equal keys");
+                }
+
+                for (i, key) in keys.into_iter().enumerate() {
+                    assert_eq!(map.get(&key), Some(&i));
+                    key.release(&env);
+                }
+
+                for (key, _) in map {
+                    key.release(&env);
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_compare_detached() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let object = env.NewStringUTF("detached");
+                let a = env.object_key(object).expect("object_key failed");
+                let b = env.weak_object_key(object).expect("weak_object_key failed");
+
+                //The thread is attached for the comparison.
+                let (a, b) = std::thread::spawn(move || {
+                    assert_eq!(a, b);
+                    (a, b)
+                })
+                .join()
+                .expect("comparing keys on a detached thread panicked");
+
+                a.release(&env);
+                b.release(&env);
+            });
+        }
+    }
+
+    #[test]
+    fn test_hash_collision() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = env.FindClass("java/lang/Object");
+                let constructor = env.GetMethodID(class, "<init>", "()V");
+                let mut by_hash: HashMap<jint, ObjectKey> = HashMap::new();
+                let mut collision = None;
+                for _ in 0..1_000_000 {
+                    let object = env.NewObject0(class, constructor);
+                    let key = env.object_key(object).expect("object_key failed");
+                    env.DeleteLocalRef(object);
+                    if let Some(existing) = by_hash.remove(&key.identity_hash()) {
+                        collision = Some((existing, key));
+                        break;
+                    }
+                    by_hash.insert(key.identity_hash(), key);
+                }
+
+                let (a, b) = collision.expect("no identity hash collision found");
+                assert_eq!(a.identity_hash(), b.identity_hash());
+                assert_ne!(a, b);
+                assert!(!a.same_object(&env, &b));
+
+                let mut map = HashMap::new();
+                let a_again = env.object_key(a.reference()).expect("object_key failed");
+                map.insert(a, 1);
+                map.insert(b, 2);
+                assert_eq!(map.len(), 2);
+                assert_eq!(map.get(&a_again), Some(&1));
+                a_again.release(&env);
+
+                for key in map.into_keys().chain(by_hash.into_values()) {
+                    key.release(&env);
+                }
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
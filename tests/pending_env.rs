@@ -0,0 +1,48 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_clear() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let EnvState::Clear(clear) = env.check_pending() else {
+                    panic!("no exception is pending");
+                };
+                let string = clear.NewStringUTF("clear");
+                assert!(!string.is_null());
+                clear.DeleteLocalRef(string);
+            });
+        }
+    }
+
+    #[test]
+    fn test_pending() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let array = env.NewIntArray(4);
+                let elements = env.GetIntArrayElements(array, null_mut());
+                assert!(!elements.is_null());
+                assert_eq!(env.ThrowNew(env.FindClass("java/lang/IllegalStateException"), "pending"), JNI_OK);
+
+                let EnvState::Pending(pending, throwable) = env.check_pending() else {
+                    panic!("an exception is pending");
+                };
+                assert!(!throwable.is_null());
+                assert!(pending.ExceptionCheck());
+                let occurred = pending.ExceptionOccurred();
+                pending.ReleaseIntArrayElements(array, elements, JNI_ABORT);
+                assert_eq!(pending.PushLocalFrame(4), JNI_OK);
+                assert!(pending.PopLocalFrame(null_mut()).is_null());
+
+                let clear = pending.ExceptionClear();
+                assert!(!clear.ExceptionCheck());
+                assert!(clear.IsSameObject(occurred, throwable));
+                clear.DeleteLocalRef(occurred);
+                clear.DeleteLocalRef(throwable);
+                assert!(matches!(clear.check_pending(), EnvState::Clear(_)));
+            });
+        }
+    }
+}
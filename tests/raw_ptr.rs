@@ -0,0 +1,58 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::collections::HashSet;
+    use std::ffi::c_void;
+
+    extern "C" fn env_shim(env: *mut c_void) -> *mut c_void {
+        env
+    }
+
+    extern "C" fn vm_shim(vm: *mut c_void) -> *mut c_void {
+        vm
+    }
+
+    #[test]
+    fn test_env_round_trip() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let raw = env.as_raw_ptr();
+                assert!(!raw.is_null());
+                let returned = env_shim(raw);
+                assert_eq!(returned, raw);
+
+                let copy = JNIEnv::from_raw_ptr(returned);
+                assert_eq!(copy, env);
+                assert_eq!(copy.as_raw_ptr(), raw);
+                assert_eq!(copy.into_raw(), raw);
+                assert_eq!(copy.GetVersion(), env.GetVersion());
+
+                let mut envs = HashSet::new();
+                assert!(envs.insert(env));
+                assert!(!envs.insert(copy));
+            });
+        }
+    }
+
+    #[test]
+    fn test_vm_round_trip() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let vm = env.GetJavaVM().expect("failed to get JavaVM");
+                let raw = vm.as_raw_ptr();
+                assert!(!raw.is_null());
+                let returned = vm_shim(raw);
+                assert_eq!(returned, raw);
+
+                let copy = JavaVM::from_raw_ptr(returned);
+                assert_eq!(copy, vm);
+                assert_eq!(copy.as_raw_ptr(), raw);
+                assert_eq!(copy.GetEnv(JNI_VERSION_1_8).expect("failed to get env"), env);
+
+                let mut vms = HashSet::new();
+                assert!(vms.insert(vm));
+                assert!(!vms.insert(copy));
+            });
+        }
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(all(feature = "test-util", feature = "asserts"))]
+pub mod test {
+    use jni_simple::*;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    const LENGTH: jsize = 4;
+
+    /// Out of bounds (start, len) pairs for an array or string of length `LENGTH`.
+    const CASES: [(jsize, jsize); 4] = [(-1, 1), (0, -1), (1, LENGTH), (jsize::MAX, jsize::MAX)];
+
+    fn panic_message(f: impl FnOnce()) -> String {
+        let payload = catch_unwind(AssertUnwindSafe(f)).expect_err("expected a panic");
+        if let Some(message) = payload.downcast_ref::<String>() {
+            return message.clone();
+        }
+
+        payload.downcast_ref::<&str>().expect("panic payload is not a string").to_string()
+    }
+
+    fn assert_region_panics(name: &str, f: impl Fn(jsize, jsize)) {
+        for (start, len) in CASES {
+            let message = panic_message(|| f(start, len));
+            assert!(message.starts_with(&format!("{name} ")), "{name} start={start} len={len} panicked with: {message}");
+            assert!(message.contains(&format!("start={start} len={len} length={LENGTH}")), "{name} panicked with: {message}");
+        }
+
+        //In bounds must not panic.
+        f(0, LENGTH);
+        f(LENGTH, 0);
+    }
+
+    macro_rules! array_matrix {
+        ($env:expr, $($new:ident $get:ident $set:ident $ty:ty),*) => {
+            $(
+                let array = $env.$new(LENGTH);
+                assert!(!array.is_null());
+                let mut buf = [<$ty>::default(); LENGTH as usize];
+                let ptr = buf.as_mut_ptr();
+                assert_region_panics(stringify!($get), |start, len| $env.$get(array, start, len, ptr));
+                assert_region_panics(stringify!($set), |start, len| $env.$set(array, start, len, ptr.cast_const()));
+                assert!(!$env.ExceptionCheck());
+                $env.DeleteLocalRef(array);
+            )*
+        };
+    }
+
+    #[test]
+    fn test_arrays() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                array_matrix!(env,
+                    NewBooleanArray GetBooleanArrayRegion SetBooleanArrayRegion jboolean,
+                    NewByteArray GetByteArrayRegion SetByteArrayRegion jbyte,
+                    NewCharArray GetCharArrayRegion SetCharArrayRegion jchar,
+                    NewShortArray GetShortArrayRegion SetShortArrayRegion jshort,
+                    NewIntArray GetIntArrayRegion SetIntArrayRegion jint,
+                    NewLongArray GetLongArrayRegion SetLongArrayRegion jlong,
+                    NewFloatArray GetFloatArrayRegion SetFloatArrayRegion jfloat,
+                    NewDoubleArray GetDoubleArrayRegion SetDoubleArrayRegion jdouble
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn test_slices() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let array = env.NewIntArray(LENGTH);
+                let mut buf = [0; 8];
+                let message = panic_message(|| env.GetIntArrayRegion_into_slice(array, 2, &mut buf[..4]));
+                assert!(message.starts_with("GetIntArrayRegion "), "{message}");
+                let message = panic_message(|| env.SetIntArrayRegion_from_slice(array, 1, &buf[..4]));
+                assert!(message.starts_with("SetIntArrayRegion "), "{message}");
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_strings() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string = env.NewStringUTF("abcd");
+                assert_eq!(env.GetStringLength(string), LENGTH);
+                let mut chars = [0u16; LENGTH as usize];
+                let chars = chars.as_mut_ptr();
+                assert_region_panics("GetStringRegion", |start, len| env.GetStringRegion(string, start, len, chars));
+                let mut utf = [0; LENGTH as usize * 3 + 1];
+                let utf = utf.as_mut_ptr();
+                assert_region_panics("GetStringUTFRegion", |start, len| env.GetStringUTFRegion(string, start, len, utf));
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(all(feature = "test-util", feature = "asserts"))]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    fn panic_message(f: impl FnOnce()) -> String {
+        let payload = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).expect_err("expected a panic");
+        payload.downcast_ref::<String>().expect("panic payload is not a String").clone()
+    }
+
+    #[test]
+    fn test_never_acquired() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let array = env.NewIntArray(4);
+                let mut memory = [0; 4];
+                let message = panic_message(|| env.ReleaseIntArrayElements(array, memory.as_mut_ptr(), JNI_ABORT));
+                assert!(message.contains("were not acquired by GetIntArrayElements on this thread"), "{message}");
+            });
+        }
+    }
+
+    #[test]
+    fn test_double_release() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let array = env.NewByteArray(4);
+                let elements = env.GetByteArrayElements(array, null_mut());
+                assert!(!elements.is_null());
+                env.ReleaseByteArrayElements(array, elements, JNI_COMMIT);
+                env.ReleaseByteArrayElements(array, elements, JNI_OK);
+                let message = panic_message(|| env.ReleaseByteArrayElements(array, elements, JNI_OK));
+                assert!(message.contains("or were already released"), "{message}");
+
+                let string = env.NewStringUTF("abc");
+                let utf = env.GetStringUTFChars(string, null_mut());
+                env.ReleaseStringUTFChars(string, utf);
+                let message = panic_message(|| env.ReleaseStringUTFChars(string, utf));
+                assert!(
+                    message.contains("were not acquired by GetStringUTFChars on this thread or were already released"),
+                    "{message}"
+                );
+            });
+        }
+    }
+
+    #[test]
+    fn test_wrong_release_function() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string = env.NewStringUTF("abc");
+                let utf = env.GetStringUTFChars(string, null_mut());
+                let message = panic_message(|| env.ReleaseStringChars(string, utf.cast()));
+                assert!(
+                    message.contains("were acquired by GetStringUTFChars and must be released by the release function that matches it"),
+                    "{message}"
+                );
+                env.ReleaseStringUTFChars(string, utf);
+
+                let chars = env.GetStringChars(string, null_mut());
+                let message = panic_message(|| env.ReleaseStringCritical(string, chars));
+                assert!(message.contains("were acquired by GetStringChars"), "{message}");
+                env.ReleaseStringChars(string, chars);
+
+                let array = env.NewLongArray(2);
+                let elements = env.GetLongArrayElements(array, null_mut());
+                let message = panic_message(|| env.ReleaseDoubleArrayElements(array, elements.cast(), JNI_ABORT));
+                assert!(message.contains("were acquired by GetLongArrayElements"), "{message}");
+                env.ReleaseLongArrayElements(array, elements, JNI_ABORT);
+
+                let array = env.NewIntArray(2);
+                let elements = env.GetIntArrayElements(array, null_mut());
+                let message = panic_message(|| env.ReleasePrimitiveArrayCritical(array, elements.cast(), JNI_ABORT));
+                assert!(message.contains("were acquired by GetIntArrayElements"), "{message}");
+                env.ReleaseIntArrayElements(array, elements, JNI_ABORT);
+            });
+        }
+    }
+
+    #[test]
+    fn test_different_owner() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let first = env.NewStringUTF("first");
+                let second = env.NewStringUTF("second");
+                let chars = env.GetStringChars(first, null_mut());
+                let message = panic_message(|| env.ReleaseStringChars(second, chars));
+                assert!(
+                    message.contains("were acquired by GetStringChars from a different string or array than the one passed"),
+                    "{message}"
+                );
+
+                //Another reference to the same string is fine.
+                let other_ref = env.NewLocalRef(first);
+                env.ReleaseStringChars(other_ref, chars);
+
+                let first = env.NewFloatArray(2);
+                let second = env.NewFloatArray(2);
+                let elements = env.GetFloatArrayElements(first, null_mut());
+                let message = panic_message(|| env.ReleaseFloatArrayElements(second, elements, JNI_OK));
+                assert!(message.contains("from a different string or array"), "{message}");
+                env.ReleaseFloatArrayElements(first, elements, JNI_OK);
+            });
+        }
+    }
+}
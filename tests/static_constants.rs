@@ -0,0 +1,81 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                assert_eq!(env.get_static_int_constant("java/lang/Integer", "MAX_VALUE"), Ok(i32::MAX));
+                assert_eq!(env.get_static_long_constant("java/lang/Long", "MIN_VALUE"), Ok(i64::MIN));
+                let separator = env.get_static_string_constant("java/io/File", "separator").unwrap().unwrap();
+                assert_eq!(separator, std::path::MAIN_SEPARATOR.to_string());
+                assert!(!env.ExceptionCheck());
+
+                let class = env.DefineClass_from_slice("FieldTests", null_mut(), include_bytes!("../java_testcode/FieldTests.class"));
+                assert!(!class.is_null());
+                env.DeleteLocalRef(class);
+                assert_eq!(env.get_static_boolean_constant("FieldTests", "staticBool"), Ok(false));
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                assert_eq!(
+                    env.get_static_int_constant("does/not/Exist", "VALUE"),
+                    Err(ConstLookupError::ClassNotFound("does/not/Exist".to_string()))
+                );
+                assert!(env.ExceptionCheck());
+                env.ExceptionClear();
+
+                assert_eq!(
+                    env.get_static_int_constant("java/lang/Integer", "DOES_NOT_EXIST"),
+                    Err(ConstLookupError::NoSuchField {
+                        class: "java/lang/Integer".to_string(),
+                        field: "DOES_NOT_EXIST".to_string(),
+                    })
+                );
+                assert!(!env.ExceptionCheck());
+
+                assert_eq!(
+                    env.get_static_long_constant("java/lang/Integer", "MAX_VALUE"),
+                    Err(ConstLookupError::WrongType {
+                        class: "java/lang/Integer".to_string(),
+                        field: "MAX_VALUE".to_string(),
+                        expected: "long".to_string(),
+                        actual: "int".to_string(),
+                    })
+                );
+                assert!(!env.ExceptionCheck());
+
+                //Instance fields are not constants.
+                assert_eq!(
+                    env.get_static_int_constant("java/lang/Integer", "value"),
+                    Err(ConstLookupError::NoSuchField {
+                        class: "java/lang/Integer".to_string(),
+                        field: "value".to_string(),
+                    })
+                );
+                assert!(!env.ExceptionCheck());
+
+                //Constants inherited from interfaces are only found through Class#getField.
+                assert_eq!(env.get_static_int_constant("java/io/ObjectOutputStream", "PROTOCOL_VERSION_2"), Ok(2));
+                assert_eq!(
+                    env.get_static_long_constant("java/io/ObjectOutputStream", "PROTOCOL_VERSION_2"),
+                    Err(ConstLookupError::WrongType {
+                        class: "java/io/ObjectOutputStream".to_string(),
+                        field: "PROTOCOL_VERSION_2".to_string(),
+                        expected: "long".to_string(),
+                        actual: "int".to_string(),
+                    })
+                );
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
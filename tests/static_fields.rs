@@ -0,0 +1,98 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe fn define(env: JNIEnv, name: &str, bytes: &[u8]) -> jclass {
+        let class = env.FindClass(name);
+        if !class.is_null() {
+            return class;
+        }
+
+        env.ExceptionClear();
+        let class = env.DefineClass_from_slice(name, null_mut(), bytes);
+        assert!(!class.is_null());
+        class
+    }
+
+    unsafe fn describe(env: JNIEnv, class: jclass) -> String {
+        let method = env.GetStaticMethodID(class, "describe", "()Ljava/lang/String;");
+        let description = env.CallStaticObjectMethod0(class, method);
+        assert!(!env.ExceptionCheck());
+        assert!(!description.is_null());
+        let mut chars = vec![0; usize::try_from(env.GetStringLength(description)).unwrap()];
+        env.GetStringRegion_into_slice(description, 0, chars.as_mut_slice());
+        env.DeleteLocalRef(description);
+        String::from_utf16(chars.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_set() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = define(env, "StaticFieldFixture", include_bytes!("../java_testcode/StaticFieldFixture.class"));
+
+                let collections = env.FindClass("java/util/Collections");
+                let singleton = env.GetStaticMethodID(collections, "singletonList", "(Ljava/lang/Object;)Ljava/util/List;");
+                let tag = env.NewStringUTF("tag");
+                let tags = env.CallStaticObjectMethod1(collections, singleton, tag);
+                assert!(!env.ExceptionCheck());
+                assert!(!tags.is_null());
+
+                env.set_static_fields(
+                    "StaticFieldFixture",
+                    &[
+                        StaticFieldValue::Int("port", 8080),
+                        StaticFieldValue::Long("budget", 1 << 40),
+                        StaticFieldValue::Bool("verbose", true),
+                        StaticFieldValue::Str("name", "agent \u{1F600}\0"),
+                        StaticFieldValue::Obj("tags", "Ljava/util/List;", tags),
+                    ],
+                )
+                .expect("failed to set static fields");
+                assert!(!env.ExceptionCheck());
+                assert_eq!(describe(env, class), "8080,1099511627776,true,agent \u{1F600}\0,[tag]");
+
+                env.set_static_fields(
+                    "StaticFieldFixture",
+                    &[StaticFieldValue::Obj("tags", "Ljava/util/List;", null_mut()), StaticFieldValue::Int("port", 1)],
+                )
+                .expect("failed to set static fields");
+                assert_eq!(describe(env, class), "1,1099511627776,true,agent \u{1F600}\0,null");
+            });
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = define(env, "StaticFieldFixture", include_bytes!("../java_testcode/StaticFieldFixture.class"));
+
+                let result = env.set_static_fields(
+                    "StaticFieldFixture",
+                    &[StaticFieldValue::Int("port", 2), StaticFieldValue::Int("name", 3), StaticFieldValue::Int("port", 4)],
+                );
+                assert_eq!(result, Err(StaticFieldError::NoSuchField("name".to_string())));
+                assert_eq!(result.unwrap_err().to_string(), "static field name not found");
+                assert!(!env.ExceptionCheck());
+                assert!(describe(env, class).starts_with("2,"));
+
+                let result = env.set_static_fields("StaticFieldFixtureMissing", &[StaticFieldValue::Int("port", 2)]);
+                assert_eq!(result, Err(StaticFieldError::ClassNotFound("StaticFieldFixtureMissing".to_string())));
+                assert!(env.ExceptionCheck());
+                env.ExceptionClear();
+
+                let broken = env.DefineClass_from_slice("StaticFieldFixtureBroken", null_mut(), include_bytes!("../java_testcode/StaticFieldFixtureBroken.class"));
+                assert!(!broken.is_null());
+                let result = env.set_static_fields("StaticFieldFixtureBroken", &[StaticFieldValue::Int("port", 2)]);
+                assert_eq!(result, Err(StaticFieldError::ClassNotFound("StaticFieldFixtureBroken".to_string())));
+                let thrown = env.ExceptionOccurred();
+                assert!(!thrown.is_null());
+                env.ExceptionClear();
+                let initializer_error = env.FindClass("java/lang/ExceptionInInitializerError");
+                assert!(env.IsInstanceOf(thrown, initializer_error));
+            });
+        }
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test_round_trip() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let items = ["hello", "", "nul\0char", "emoji \u{1F600}", "umlaut äöü"];
+                let array = env.new_string_array(&items);
+                assert!(!array.is_null());
+                assert_eq!(env.GetArrayLength(array), 5);
+
+                let result = env.string_array_to_vec(array).unwrap();
+                let expected = items.iter().map(|s| Some((*s).to_string())).collect::<Vec<_>>();
+                assert_eq!(result, expected);
+                env.DeleteLocalRef(array);
+            });
+        }
+    }
+
+    #[test]
+    fn test_empty() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let array = env.new_string_array(&Vec::<String>::new());
+                assert!(!array.is_null());
+                assert_eq!(env.GetArrayLength(array), 0);
+                assert_eq!(env.string_array_to_vec(array), Some(Vec::new()));
+                env.DeleteLocalRef(array);
+            });
+        }
+    }
+
+    #[test]
+    fn test_null_element() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let array = env.new_string_array(&[String::from("a"), String::from("b")]);
+                env.SetObjectArrayElement(array, 0, std::ptr::null_mut());
+                assert_eq!(env.string_array_to_vec(array), Some(vec![None, Some(String::from("b"))]));
+                env.DeleteLocalRef(array);
+            });
+        }
+    }
+}
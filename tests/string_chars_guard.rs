@@ -0,0 +1,55 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+
+    #[test]
+    fn test_both_paths() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let text = "h\u{E4}llo \u{20AC} ".repeat(10_000);
+                let expected = text.encode_utf16().collect::<Vec<_>>();
+                let string = env.NewStringUTF(text.as_str());
+
+                for prefer_critical in [true, false] {
+                    let guard = env.string_chars_guard(string, prefer_critical).expect("string_chars_guard failed");
+                    if !prefer_critical {
+                        assert!(!guard.is_critical());
+                    }
+                    assert_eq!(guard.as_slice(), expected.as_slice());
+                    drop(guard);
+
+                    //Released, so other JNI calls work again.
+                    let class = env.FindClass("java/lang/String");
+                    assert!(!class.is_null());
+                    env.DeleteLocalRef(class);
+                }
+
+                let empty = env.NewStringUTF("");
+                let guard = env.string_chars_guard(empty, true).expect("string_chars_guard failed");
+                assert!(guard.as_slice().is_empty());
+                drop(guard);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[cfg(feature = "asserts")]
+    #[test]
+    fn test_critical_region() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string = env.NewStringUTF("critical");
+                let guard = env.string_chars_guard(string, true).expect("string_chars_guard failed");
+                assert!(guard.is_critical());
+                let result = std::panic::catch_unwind(|| env.FindClass("java/lang/String"));
+                assert!(result.is_err(), "JNI call inside critical region was not detected");
+                drop(guard);
+
+                let guard = env.string_chars_guard(string, false).expect("string_chars_guard failed");
+                let class = env.FindClass("java/lang/String");
+                assert!(!class.is_null());
+                drop(guard);
+            });
+        }
+    }
+}
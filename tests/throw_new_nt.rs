@@ -1,4 +1,4 @@
-#[cfg(feature = "loadjvm")]
+#[cfg(feature = "test-util")]
 #[cfg(feature = "asserts")]
 pub mod test {
     use jni_simple::*;
@@ -7,16 +7,14 @@ pub mod test {
     #[test]
     fn test() {
         unsafe {
-            load_jvm_from_java_home().expect("failed to load jvm");
-            let args: Vec<String> = vec!["-Xcheck:jni".to_string()];
-            let (vm, env) = JNI_CreateJavaVM_with_string_args(JNI_VERSION_1_8, &args).expect("failed to create java VM");
-            let obj_cl = env.FindClass("java/lang/Object");
+            testing::with_test_frame(|env| {
+                let obj_cl = env.FindClass("java/lang/Object");
 
-            let n = panic::catch_unwind(|| env.ThrowNew(obj_cl, ()));
-            assert!(n.is_err());
+                let n = panic::catch_unwind(|| env.ThrowNew(obj_cl, ()));
+                assert!(n.is_err());
 
-            env.DeleteLocalRef(obj_cl);
-            vm.DestroyJavaVM();
+                env.DeleteLocalRef(obj_cl);
+            });
         }
     }
 }
@@ -0,0 +1,76 @@
+#[cfg(all(feature = "test-util", unix))]
+pub mod test {
+    use jni_simple::*;
+    use std::ffi::{c_double, c_int, c_longlong, c_void};
+    use std::ptr::null_mut;
+
+    type ObjectCallback = unsafe extern "C" fn(*mut c_void, jobject, jmethodID, va_list) -> jobject;
+    type IntCallback = unsafe extern "C" fn(*mut c_void, jobject, jmethodID, va_list) -> jint;
+    type ShimObject = unsafe extern "C" fn(*mut c_void, jobject, jmethodID, ObjectCallback, ...) -> jobject;
+    type ShimInt = unsafe extern "C" fn(*mut c_void, jobject, jmethodID, IntCallback, ...) -> jint;
+
+    unsafe extern "C" fn call_static_object(env: *mut c_void, class: jobject, method: jmethodID, args: va_list) -> jobject {
+        JNIEnv::from_raw_ptr(env).CallStaticObjectMethodV(class, method, args)
+    }
+
+    unsafe extern "C" fn new_object(env: *mut c_void, class: jobject, constructor: jmethodID, args: va_list) -> jobject {
+        JNIEnv::from_raw_ptr(env).NewObjectV(class, constructor, args)
+    }
+
+    unsafe extern "C" fn call_int(env: *mut c_void, obj: jobject, method: jmethodID, args: va_list) -> jint {
+        JNIEnv::from_raw_ptr(env).CallIntMethodV(obj, method, args)
+    }
+
+    unsafe extern "C" fn call_nonvirtual_int(env: *mut c_void, obj: jobject, method: jmethodID, args: va_list) -> jint {
+        let env = JNIEnv::from_raw_ptr(env);
+        let class = env.GetObjectClass(obj);
+        let result = env.CallNonvirtualIntMethodV(obj, class, method, args);
+        env.DeleteLocalRef(class);
+        result
+    }
+
+    #[test]
+    fn test_va_list() {
+        unsafe {
+            //Compiled by the build script.
+            let library = libloading::Library::new(env!("JNI_SIMPLE_VA_LIST_SHIM")).expect("failed to load shim");
+            let shim_object = *library.get::<ShimObject>(b"shim_object\0").expect("shim_object not found");
+            let shim_int = *library.get::<ShimInt>(b"shim_int\0").expect("shim_int not found");
+
+            testing::with_test_frame(|env| {
+                let class = env.DefineClass_from_slice("VaListTarget", null_mut(), include_bytes!("../java_testcode/VaListTarget.class"));
+                assert!(!class.is_null());
+                let raw = env.as_raw_ptr();
+
+                let describe = env.GetStaticMethodID(class, "describe", "(IJFDZCLjava/lang/String;)Ljava/lang/String;");
+                let text = env.NewStringUTF("text");
+                let description = shim_object(
+                    raw,
+                    class,
+                    describe,
+                    call_static_object,
+                    -7 as c_int,
+                    (1 as c_longlong) << 40,
+                    1.5 as c_double,
+                    -2.25 as c_double,
+                    c_int::from(true),
+                    c_int::from(b'x'),
+                    text,
+                );
+                assert!(!env.ExceptionCheck());
+                assert_eq!(env.GetStringUTFChars_as_string(description).as_deref(), Some("-7,1099511627776,1.5,-2.25,true,x,text"));
+
+                let constructor = env.GetMethodID(class, "<init>", "(IJ)V");
+                let obj = shim_object(raw, class, constructor, new_object, 40 as c_int, 2 as c_longlong);
+                assert!(!env.ExceptionCheck());
+                assert!(!obj.is_null());
+
+                let add = env.GetMethodID(class, "add", "(ISB)I");
+                assert_eq!(shim_int(raw, obj, add, call_int, 1 as c_int, -2 as c_int, 3 as c_int), 44);
+                assert!(!env.ExceptionCheck());
+                assert_eq!(shim_int(raw, obj, add, call_nonvirtual_int, 10 as c_int, 20 as c_int, 30 as c_int), 102);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+}
@@ -0,0 +1,21 @@
+/* Compiled by tests/va_list.rs. Each function collects its variadic arguments into a va_list and passes it to a rust callback. */
+#include <stdarg.h>
+
+typedef void *(*object_callback)(void *env, void *target, void *method, va_list args);
+typedef int (*int_callback)(void *env, void *target, void *method, va_list args);
+
+void *shim_object(void *env, void *target, void *method, object_callback callback, ...) {
+    va_list args;
+    va_start(args, callback);
+    void *result = callback(env, target, method, args);
+    va_end(args);
+    return result;
+}
+
+int shim_int(void *env, void *target, void *method, int_callback callback, ...) {
+    va_list args;
+    va_start(args, callback);
+    int result = callback(env, target, method, args);
+    va_end(args);
+    return result;
+}
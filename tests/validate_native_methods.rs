@@ -0,0 +1,128 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe extern "system" fn add(_env: JNIEnv, _class: jclass, a: jint, b: jint) -> jint {
+        a + b
+    }
+
+    unsafe extern "system" fn greet(env: JNIEnv, _this: jobject, name: jstring) -> jstring {
+        let name = env.GetStringUTFChars_as_string(name).unwrap_or_default();
+        env.NewStringUTF(format!("Hello {name}"))
+    }
+
+    unsafe extern "system" fn sum(_env: JNIEnv, _this: jobject, _values: jlongArray) -> jlong {
+        0
+    }
+
+    const WRONG_SIGNATURE: [JNINativeMethod; 2] = [
+        native_method!("add", "(IJ)I", fn add(env: JNIEnv, class: jclass, a: jint, b: jint) -> jint),
+        native_method!("greet", "(Ljava/lang/String;)Ljava/lang/String;", fn greet(env: JNIEnv, this: jobject, name: jstring) -> jstring),
+    ];
+
+    const CORRECT: [JNINativeMethod; 3] = [
+        native_method!("add", "(II)I", fn add(env: JNIEnv, class: jclass, a: jint, b: jint) -> jint),
+        native_method!("greet", "(Ljava/lang/String;)Ljava/lang/String;", fn greet(env: JNIEnv, this: jobject, name: jstring) -> jstring),
+        native_method!("sum", "([J)J", fn sum(env: JNIEnv, this: jobject, values: jlongArray) -> jlong),
+    ];
+
+    //Single test, because the fixture class can only be defined once per JVM.
+    #[test]
+    fn test_validate_and_register() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let class = env.DefineClass_from_slice("NativeValidationFixture", null_mut(), include_bytes!("../java_testcode/NativeValidationFixture.class"));
+                assert!(!class.is_null());
+
+                let mismatches = env.validate_native_methods(
+                    class,
+                    &[
+                        NativeMethodDesc::new_static("add", "(II)I"),
+                        NativeMethodDesc::new("greet", "(Ljava/lang/String;)Ljava/lang/String;"),
+                        NativeMethodDesc::new_instance("sum", "([J)J"),
+                        NativeMethodDesc::new("add", "(IJ)I"),
+                        NativeMethodDesc::new("missing", "()V"),
+                        NativeMethodDesc::new("notNative", "(I)I"),
+                        NativeMethodDesc::new_instance("add", "(II)I"),
+                        NativeMethodDesc::new_static("greet", "(Ljava/lang/String;)Ljava/lang/String;"),
+                    ],
+                );
+                assert!(!env.ExceptionCheck());
+                assert_eq!(
+                    mismatches,
+                    vec![
+                        NativeMethodMismatch::NotFound {
+                            name: "add".to_string(),
+                            signature: "(IJ)I".to_string(),
+                        },
+                        NativeMethodMismatch::NotFound {
+                            name: "missing".to_string(),
+                            signature: "()V".to_string(),
+                        },
+                        NativeMethodMismatch::NotNative {
+                            name: "notNative".to_string(),
+                            signature: "(I)I".to_string(),
+                        },
+                        NativeMethodMismatch::StaticnessDiffers {
+                            name: "add".to_string(),
+                            signature: "(II)I".to_string(),
+                            is_static: true,
+                        },
+                        NativeMethodMismatch::StaticnessDiffers {
+                            name: "greet".to_string(),
+                            signature: "(Ljava/lang/String;)Ljava/lang/String;".to_string(),
+                            is_static: false,
+                        },
+                    ]
+                );
+                assert_eq!(mismatches[0].name(), "add");
+                assert_eq!(mismatches[0].signature(), "(IJ)I");
+
+                let error = env.RegisterNatives_validated(class, &WRONG_SIGNATURE).expect_err("signature of add is wrong");
+                assert!(!env.ExceptionCheck());
+                assert_eq!(
+                    error,
+                    RegisterNativesError::Mismatch(vec![NativeMethodMismatch::NotFound {
+                        name: "add".to_string(),
+                        signature: "(IJ)I".to_string(),
+                    }])
+                );
+                assert_eq!(error.to_string(), "native methods do not match the class: method add(IJ)I not found;");
+
+                //Nothing was registered.
+                let call_greet = env.GetStaticMethodID(class, "callGreet", "(Ljava/lang/String;)Ljava/lang/String;");
+                let name = env.NewStringUTF("validated");
+                let greeting = env.CallStaticObjectMethod1(class, call_greet, name);
+                assert!(greeting.is_null());
+                assert!(env.ExceptionCheck());
+                env.ExceptionClear();
+
+                env.RegisterNatives_validated(class, &CORRECT).expect("all methods match");
+                let greeting = env.CallStaticObjectMethod1(class, call_greet, name);
+                assert!(!env.ExceptionCheck());
+                assert_eq!(env.GetStringUTFChars_as_string(greeting).as_deref(), Some("Hello validated"));
+
+                let add = env.GetStaticMethodID(class, "add", "(II)I");
+                assert_eq!(env.CallStaticIntMethod2(class, add, 20, 22), 42);
+                assert!(!env.ExceptionCheck());
+            });
+        }
+    }
+
+    #[test]
+    fn test_mismatch_display() {
+        let mismatch = |is_static| NativeMethodMismatch::StaticnessDiffers {
+            name: "add".to_string(),
+            signature: "(II)I".to_string(),
+            is_static,
+        };
+        assert_eq!(mismatch(true).to_string(), "method add(II)I is static but an instance method was expected");
+        assert_eq!(mismatch(false).to_string(), "method add(II)I is an instance method but a static method was expected");
+        let not_native = NativeMethodMismatch::NotNative {
+            name: "notNative".to_string(),
+            signature: "(I)I".to_string(),
+        };
+        assert_eq!(not_native.to_string(), "method notNative(I)I is not declared native");
+    }
+}
@@ -0,0 +1,34 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ffi::c_void;
+
+    #[test]
+    fn test_owning_vm() {
+        unsafe {
+            let (vm, env) = testing::test_vm();
+            let owner = env.owning_vm().expect("GetJavaVM failed");
+            assert!(owner.ptr_eq(&vm));
+            assert_eq!(owner, vm);
+            assert!(env.same_vm(&vm));
+
+            let other = JavaVM::from_raw_ptr(std::ptr::NonNull::<c_void>::dangling().as_ptr());
+            assert!(!owner.ptr_eq(&other));
+            assert!(!env.same_vm(&other));
+        }
+    }
+
+    #[test]
+    fn test_get_or_init() {
+        unsafe {
+            let local: VmLocal<jint> = VmLocal::new();
+            testing::with_test_frame(|env| {
+                let version = *local.get_or_init(&env, |env| env.GetVersion());
+                assert_eq!(version, env.GetVersion());
+                assert_eq!(*local.get_or_init(&env, |_| unreachable!("value exists")), version);
+                assert_eq!(local.len(), 1);
+                assert_eq!(local.get(&env.owning_vm().expect("GetJavaVM failed")), Some(&version));
+            });
+        }
+    }
+}
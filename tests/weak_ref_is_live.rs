@@ -0,0 +1,110 @@
+#[cfg(feature = "test-util")]
+pub mod test {
+    use jni_simple::*;
+    use std::ptr::null_mut;
+
+    unsafe fn gc(env: JNIEnv) {
+        let system = env.FindClass("java/lang/System");
+        let gc = env.GetStaticMethodID(system, "gc", "()V");
+        env.CallStaticVoidMethod0(system, gc);
+        assert!(!env.ExceptionCheck());
+        env.DeleteLocalRef(system);
+    }
+
+    #[test]
+    fn test_refs_equal() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let a = env.NewStringUTF("a");
+                let b = env.NewStringUTF("b");
+                let a_global = env.NewGlobalRef(a);
+                assert!(env.refs_equal(a, a_global));
+                assert!(!env.refs_equal(a, b));
+                assert!(!env.refs_equal(a, null_mut()));
+                assert!(env.refs_equal(null_mut(), null_mut()));
+                env.DeleteGlobalRef(a_global);
+            });
+        }
+    }
+
+    #[test]
+    fn test_collected() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let object_class = env.FindClass("java/lang/Object");
+                let object = env.AllocObject(object_class);
+                let weak = env.NewWeakGlobalRef(object);
+                assert!(env.weak_ref_is_live(weak));
+
+                gc(env);
+                assert!(env.weak_ref_is_live(weak), "strong local ref keeps the object alive");
+
+                env.DeleteLocalRef(object);
+                let mut live = true;
+                for _ in 0..100 {
+                    gc(env);
+                    live = env.weak_ref_is_live(weak);
+                    if !live {
+                        break;
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                assert!(!live, "object was not collected");
+                assert!(env.refs_equal(weak, null_mut()));
+                assert!(env.NewLocalRef(weak).is_null());
+
+                env.DeleteWeakGlobalRef(weak);
+            });
+        }
+    }
+
+    #[test]
+    fn test_call_on_weak() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let string = env.NewStringUTF("listener");
+                let weak = env.NewWeakGlobalRef(string);
+                let string_class = env.GetObjectClass(string);
+                let length = env.GetMethodID(string_class, "length", "()I");
+
+                assert_eq!(env.call_on_weak(weak, |env, listener| env.CallIntMethod0(listener, length)), Some(8));
+                assert!(!env.ExceptionCheck());
+
+                //The closure may leave an exception pending.
+                let thrown = env.call_on_weak(weak, |env, _| env.ThrowNew(env.FindClass("java/lang/IllegalStateException"), "listener failed"));
+                assert_eq!(thrown, Some(JNI_OK));
+                assert!(env.ExceptionCheck());
+                env.ExceptionClear();
+
+                env.DeleteLocalRef(string);
+                let mut collected = false;
+                for _ in 0..100 {
+                    gc(env);
+                    if env.call_on_weak(weak, |_, _| ()).is_none() {
+                        collected = true;
+                        break;
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(10));
+                }
+                assert!(collected, "object was not collected");
+                assert_eq!(env.call_on_weak(weak, |_, _| panic!("called for a collected object")), None::<()>);
+
+                env.DeleteWeakGlobalRef(weak);
+            });
+        }
+    }
+
+    #[cfg(feature = "asserts")]
+    #[test]
+    fn test_not_weak() {
+        unsafe {
+            testing::with_test_frame(|env| {
+                let local = env.NewStringUTF("local");
+                let result = std::panic::catch_unwind(|| env.weak_ref_is_live(local));
+                assert!(result.is_err());
+                let result = std::panic::catch_unwind(|| env.call_on_weak(local, |_, _| ()));
+                assert!(result.is_err());
+            });
+        }
+    }
+}